@@ -0,0 +1,45 @@
+/*
+Copyright (C) 2025 Mateusz Mazur (Mazurel) <mateusz.mazur@e.email>
+
+This program is free software; you can redistribute it and/or
+modify it under the terms of the GNU General Public License
+as published by the Free Software Foundation; either version 2
+of the License, or (at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program; if not, see
+<https://www.gnu.org/licenses/>.
+*/
+
+use crate::cookies;
+use libsopa::audit::{self, Timestamp};
+
+const EDITOR_NAME_COOKIE: &str = "editor-name";
+
+fn browser_clock() -> Timestamp {
+    Timestamp(js_sys::Date::now() as i64)
+}
+
+/// Sets name of the person editing locations in this browser, see `audit::set_editor_name`.
+pub fn set_editor_name(name: &str) -> Result<(), String> {
+    audit::set_editor_name(name);
+    let encoded_name: String = js_sys::encode_uri_component(name.trim()).into();
+    cookies::set_cookie(EDITOR_NAME_COOKIE, &encoded_name)
+}
+
+/// System clock of the standard library is not available in the browser,
+/// so it is replaced before any location is modified.
+pub fn init_editor_settings() {
+    audit::set_clock(browser_clock);
+    let cookie_editor_name = cookies::get_cookie(EDITOR_NAME_COOKIE)
+        .ok()
+        .and_then(|name| js_sys::decode_uri_component(&name).ok());
+    if let Some(name) = cookie_editor_name {
+        audit::set_editor_name(&String::from(name));
+    }
+}
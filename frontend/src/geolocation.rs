@@ -0,0 +1,62 @@
+/*
+Copyright (C) 2025 Mateusz Mazur (Mazurel) <mateusz.mazur@e.email>
+
+This program is free software; you can redistribute it and/or
+modify it under the terms of the GNU General Public License
+as published by the Free Software Foundation; either version 2
+of the License, or (at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program; if not, see
+<https://www.gnu.org/licenses/>.
+*/
+
+use js_sys::{wasm_bindgen::prelude::*, Function};
+use libsopa::geo::GeoPoint;
+use log::*;
+use web_sys::Position;
+use yew::Callback;
+
+/// Asks the browser for the current position of the user.
+///
+/// `position_cb` gets `None` when the position is unavailable,
+/// e.g. when the user denied the permission.
+pub fn request_current_position(position_cb: Callback<Option<GeoPoint>>) {
+    let geolocation = web_sys::window().and_then(|window| window.navigator().geolocation().ok());
+    let Some(geolocation) = geolocation else {
+        warn!("Geolocation is not supported by the browser");
+        position_cb.emit(None);
+        return;
+    };
+
+    let on_success: Function = {
+        let position_cb = position_cb.clone();
+        Closure::<dyn Fn(Position)>::new(move |position: Position| {
+            let coords = position.coords();
+            position_cb.emit(GeoPoint::new(coords.latitude(), coords.longitude()));
+        })
+        .into_js_value()
+        .unchecked_into()
+    };
+    let on_error: Function = {
+        let position_cb = position_cb.clone();
+        Closure::<dyn Fn(JsValue)>::new(move |err: JsValue| {
+            warn!("Failed to get current position: {err:?}");
+            position_cb.emit(None);
+        })
+        .into_js_value()
+        .unchecked_into()
+    };
+
+    if let Err(err) =
+        geolocation.get_current_position_with_error_callback(&on_success, Some(&on_error))
+    {
+        warn!("Failed to request current position: {err:?}");
+        position_cb.emit(None);
+    }
+}
@@ -16,31 +16,62 @@ along with this program; if not, see
 <https://www.gnu.org/licenses/>.
 */
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 use deli::{Database, Error, Model, Transaction};
+use libsopa::journal::{Journal, JournaledLocations, Operation};
 use libsopa::locations::{Location, Locations};
+use libsopa::organisations::Organisation;
 use log::*;
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use yew::platform::spawn_local;
 use yew::prelude::*;
 
 const LOCATIONS_STORE_NAME: &str = "locations";
+const LOCATIONS_STORE_VERSION: u32 = 3;
+const JOURNAL_KEY: u32 = 0;
+const ORGANISATIONS_KEY: u32 = 0;
+
+/// Journal is kept in the IndexedDB as a single record.
+#[derive(Serialize, Deserialize, Model)]
+struct StoredJournal {
+    #[deli(key)]
+    id: u32,
+    journal: Journal,
+}
 
-fn create_read_transaction(database: &Database) -> Result<Transaction, Error> {
-    database.transaction().with_model::<Location>().build()
+/// Organisations are few, so they are kept in the IndexedDB as a single record.
+#[derive(Serialize, Deserialize, Model)]
+struct StoredOrganisations {
+    #[deli(key)]
+    id: u32,
+    organisations: HashMap<Uuid, Organisation>,
 }
 
-fn create_write_transaction(database: &Database) -> Result<Transaction, Error> {
-    database
-        .transaction()
-        .writable()
-        .with_model::<Location>()
+async fn open_indexed_db() -> Result<Database, String> {
+    // Open the database, creating it if needed
+    Database::builder(LOCATIONS_STORE_NAME)
+        .version(LOCATIONS_STORE_VERSION)
+        .add_model::<Location>()
+        .add_model::<StoredJournal>()
+        .add_model::<StoredOrganisations>()
         .build()
+        .await
+        .map_err(|err| format!("Failed opening database: {err:?}"))
+}
+
+fn create_read_transaction<M: Model>(database: &Database) -> Result<Transaction, Error> {
+    database.transaction().with_model::<M>().build()
+}
+
+fn create_write_transaction<M: Model>(database: &Database) -> Result<Transaction, Error> {
+    database.transaction().writable().with_model::<M>().build()
 }
 
 pub struct LocationsWrapper {
     locations: Locations,
+    journal: Journal,
     locations_changed_callbacks: Vec<Callback<()>>,
 }
 
@@ -70,6 +101,7 @@ impl LocationsDatabase {
             id: uuid::Uuid::new_v4(),
             locations: std::sync::Arc::new(std::sync::RwLock::new(LocationsWrapper {
                 locations: Locations::new(),
+                journal: Journal::new(),
                 locations_changed_callbacks: vec![],
             })),
         };
@@ -79,6 +111,8 @@ impl LocationsDatabase {
             spawn_local(async move {
                 let mut new_self: LocationsDatabase = new_self.clone();
                 new_self.fetch_locations_from_indexed_db_wrapped().await;
+                new_self.fetch_journal_from_indexed_db_wrapped().await;
+                new_self.fetch_organisations_from_indexed_db_wrapped().await;
             });
         }
 
@@ -86,13 +120,7 @@ impl LocationsDatabase {
     }
 
     async fn backup_locations_in_indexed_db(&self) -> Result<(), String> {
-        // Open the database, creating it if needed
-        let db = Database::builder(LOCATIONS_STORE_NAME)
-            .version(1)
-            .add_model::<Location>()
-            .build()
-            .await
-            .map_err(|err| format!("Failed opening database: {err:?}"))?;
+        let db = open_indexed_db().await?;
 
         {
             let locations = {
@@ -102,7 +130,7 @@ impl LocationsDatabase {
             let mut used_locations_ids: HashSet<Uuid> = HashSet::with_capacity(locations.len());
 
             {
-                let write_transaction = create_write_transaction(&db)
+                let write_transaction = create_write_transaction::<Location>(&db)
                     .map_err(|err| format!("Failed creating write transaction: {err:?}"))?;
                 let location_write_transaction = Location::with_transaction(&write_transaction)
                     .map_err(|err| format!("Failed creating location transaction: {err:?}"))?;
@@ -113,6 +141,13 @@ impl LocationsDatabase {
                         .await
                         .map_err(|err| format!("Failed getting location: {err:?}"))?
                     {
+                        Some(stored_location)
+                            if stored_location.get_revision() > location.get_revision() =>
+                        {
+                            // Location was changed in other tab, it will be fetched
+                            // during next synchronization instead of being overwritten.
+                            info!("Skipping outdated location: {:?}", location.get_id());
+                        }
                         Some(_) => {
                             // Update
                             location_write_transaction
@@ -136,7 +171,7 @@ impl LocationsDatabase {
             }
 
             let all_keys = {
-                let read_transaction = create_read_transaction(&db)
+                let read_transaction = create_read_transaction::<Location>(&db)
                     .map_err(|err| format!("Failed creating read transaction: {err:?}"))?;
                 let location_read_transaction = Location::with_transaction(&read_transaction)
                     .map_err(|err| format!("Failed creating location transaction: {err:?}"))?;
@@ -152,7 +187,7 @@ impl LocationsDatabase {
                 .collect();
 
             {
-                let write_transaction = create_write_transaction(&db)
+                let write_transaction = create_write_transaction::<Location>(&db)
                     .map_err(|err| format!("Failed creating write transaction: {err:?}"))?;
                 let location_write_transaction = Location::with_transaction(&write_transaction)
                     .map_err(|err| format!("Failed creating location transaction: {err:?}"))?;
@@ -175,15 +210,9 @@ impl LocationsDatabase {
     }
 
     async fn fetch_locations_from_indexed_db(&mut self) -> Result<(), String> {
-        // Open the database, creating it if needed
-        let db = Database::builder(LOCATIONS_STORE_NAME)
-            .version(1)
-            .add_model::<Location>()
-            .build()
-            .await
-            .map_err(|err| format!("Failed opening database: {err:?}"))?;
+        let db = open_indexed_db().await?;
 
-        let read_transaction = create_read_transaction(&db)
+        let read_transaction = create_read_transaction::<Location>(&db)
             .map_err(|err| format!("Failed creating read transaction: {err:?}"))?;
         let location_transaction = Location::with_transaction(&read_transaction)
             .map_err(|err| format!("Failed creating location transaction: {err:?}"))?;
@@ -195,18 +224,117 @@ impl LocationsDatabase {
 
         self.use_locations_mut_without_indexed_db(move |locations| {
             for location in all_locations {
-                locations.push_update(location);
+                locations.restore(location);
             }
         });
 
         Ok(())
     }
 
+    async fn backup_journal_in_indexed_db(&self) -> Result<(), String> {
+        let db = open_indexed_db().await?;
+        let stored_journal = StoredJournal {
+            id: JOURNAL_KEY,
+            journal: self.locations.read().unwrap().journal.clone(),
+        };
+
+        let write_transaction = create_write_transaction::<StoredJournal>(&db)
+            .map_err(|err| format!("Failed creating write transaction: {err:?}"))?;
+        StoredJournal::with_transaction(&write_transaction)
+            .map_err(|err| format!("Failed creating journal transaction: {err:?}"))?
+            .update(&stored_journal)
+            .await
+            .map_err(|err| format!("Failed storing journal: {err:?}"))?;
+
+        Ok(())
+    }
+
+    async fn fetch_journal_from_indexed_db(&mut self) -> Result<(), String> {
+        let db = open_indexed_db().await?;
+
+        let read_transaction = create_read_transaction::<StoredJournal>(&db)
+            .map_err(|err| format!("Failed creating read transaction: {err:?}"))?;
+        let stored_journal = StoredJournal::with_transaction(&read_transaction)
+            .map_err(|err| format!("Failed creating journal transaction: {err:?}"))?
+            .get(&JOURNAL_KEY)
+            .await
+            .map_err(|err| format!("Failed reading journal: {err:?}"))?;
+
+        if let Some(stored_journal) = stored_journal {
+            self.locations.write().unwrap().journal = stored_journal.journal;
+            self.notify_about_db_update();
+        }
+
+        Ok(())
+    }
+
+    async fn backup_organisations_in_indexed_db(&self) -> Result<(), String> {
+        let db = open_indexed_db().await?;
+        let stored_organisations = StoredOrganisations {
+            id: ORGANISATIONS_KEY,
+            organisations: self
+                .locations
+                .read()
+                .unwrap()
+                .locations
+                .organisations
+                .clone(),
+        };
+
+        let write_transaction = create_write_transaction::<StoredOrganisations>(&db)
+            .map_err(|err| format!("Failed creating write transaction: {err:?}"))?;
+        StoredOrganisations::with_transaction(&write_transaction)
+            .map_err(|err| format!("Failed creating organisations transaction: {err:?}"))?
+            .update(&stored_organisations)
+            .await
+            .map_err(|err| format!("Failed storing organisations: {err:?}"))?;
+
+        Ok(())
+    }
+
+    async fn fetch_organisations_from_indexed_db(&mut self) -> Result<(), String> {
+        let db = open_indexed_db().await?;
+
+        let read_transaction = create_read_transaction::<StoredOrganisations>(&db)
+            .map_err(|err| format!("Failed creating read transaction: {err:?}"))?;
+        let stored_organisations = StoredOrganisations::with_transaction(&read_transaction)
+            .map_err(|err| format!("Failed creating organisations transaction: {err:?}"))?
+            .get(&ORGANISATIONS_KEY)
+            .await
+            .map_err(|err| format!("Failed reading organisations: {err:?}"))?;
+
+        if let Some(stored_organisations) = stored_organisations {
+            self.use_locations_mut_without_indexed_db(move |locations| {
+                locations.organisations = stored_organisations.organisations;
+            });
+        }
+
+        Ok(())
+    }
+
     async fn backup_locations_in_indexed_db_wrapped(&self) {
         match self.backup_locations_in_indexed_db().await {
             Ok(_) => (),
             Err(err) => warn!("Failed backing up location in IndexedDB: {err:?}"),
         }
+        if let Err(err) = self.backup_journal_in_indexed_db().await {
+            warn!("Failed backing up journal in IndexedDB: {err:?}");
+        }
+        if let Err(err) = self.backup_organisations_in_indexed_db().await {
+            warn!("Failed backing up organisations in IndexedDB: {err:?}");
+        }
+    }
+
+    async fn fetch_journal_from_indexed_db_wrapped(&mut self) {
+        if let Err(err) = self.fetch_journal_from_indexed_db().await {
+            warn!("Failed fetching journal from IndexedDB: {err:?}");
+        }
+    }
+
+    async fn fetch_organisations_from_indexed_db_wrapped(&mut self) {
+        if let Err(err) = self.fetch_organisations_from_indexed_db().await {
+            warn!("Failed fetching organisations from IndexedDB: {err:?}");
+        }
     }
 
     async fn fetch_locations_from_indexed_db_wrapped(&mut self) {
@@ -226,12 +354,12 @@ impl LocationsDatabase {
         }
     }
 
-    pub fn reload_database_from_bin(&self, bin_data: Vec<u8>) {
-        {
-            let locations = &mut self.locations.write().unwrap().locations;
-            *locations = Locations::from_bin_data(bin_data);
-        }
-        self.notify_about_db_update();
+    pub fn reload_database_from_bin(&self, bin_data: Vec<u8>) -> libsopa::error::Result<()> {
+        let new_locations = Locations::from_bin_data(bin_data)?;
+        self.clone().use_locations_mut(move |locations| {
+            locations.reload(new_locations);
+        });
+        Ok(())
     }
 
     pub fn load_default_database() -> Self {
@@ -240,7 +368,8 @@ impl LocationsDatabase {
         let mut database = Self::new();
         // We do not want to overwrite whatever is in indexed db
         database.use_locations_mut_without_indexed_db(move |locations| {
-            *locations = Locations::from_bin_data(database_raw);
+            *locations = Locations::from_bin_data(database_raw)
+                .expect("Embedded initial database should be valid");
         });
         database
     }
@@ -253,11 +382,41 @@ impl LocationsDatabase {
         use_fn(&locations);
     }
 
+    pub fn use_journal<F>(&self, use_fn: F)
+    where
+        F: FnOnce(&Journal),
+    {
+        let journal = &self.locations.read().unwrap().journal;
+        use_fn(journal);
+    }
+
+    /// Reverts the last operation done on the database.
+    pub fn undo(&mut self) -> Option<Operation> {
+        let mut undone_operation = None;
+        self.use_locations_mut(|locations| undone_operation = locations.undo());
+        undone_operation
+    }
+
+    /// Applies again the last undone operation.
+    pub fn redo(&mut self) -> Option<Operation> {
+        let mut redone_operation = None;
+        self.use_locations_mut(|locations| redone_operation = locations.redo());
+        redone_operation
+    }
+
+    /// Allows to modify locations, every modification is recorded
+    /// in the journal and backed up in the IndexedDB.
     pub fn use_locations_mut<F>(&mut self, use_fn: F)
     where
-        F: FnOnce(&mut Locations),
+        F: FnOnce(&mut JournaledLocations),
     {
-        self.use_locations_mut_without_indexed_db(use_fn);
+        {
+            let wrapper = &mut *self.locations.write().unwrap();
+            let mut journaled_locations =
+                JournaledLocations::new(&mut wrapper.locations, &mut wrapper.journal);
+            use_fn(&mut journaled_locations);
+        }
+        self.notify_about_db_update();
         let database: LocationsDatabase = self.clone();
         spawn_local(async move {
             let database: LocationsDatabase = database.clone();
@@ -265,6 +424,20 @@ impl LocationsDatabase {
         });
     }
 
+    /// Works like `use_locations_mut`, but first fetches locations
+    /// changed in IndexedDB (e.g. by other browser tab), so that `use_fn`
+    /// works on the newest known revisions.
+    pub fn use_locations_mut_synced<F>(&self, use_fn: F)
+    where
+        F: FnOnce(&mut JournaledLocations) + 'static,
+    {
+        let mut database: LocationsDatabase = self.clone();
+        spawn_local(async move {
+            database.fetch_locations_from_indexed_db_wrapped().await;
+            database.use_locations_mut(use_fn);
+        });
+    }
+
     fn use_locations_mut_without_indexed_db<F>(&mut self, use_fn: F)
     where
         F: FnOnce(&mut Locations),
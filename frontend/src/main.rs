@@ -22,7 +22,9 @@ mod app;
 mod color_scheme;
 mod cookies;
 mod download;
+mod editor;
 mod footer;
+mod geolocation;
 mod language;
 mod locations;
 mod notifications;
@@ -39,6 +41,7 @@ i18n!("../locales");
 fn main() {
     wasm_logger::init(wasm_logger::Config::default());
     language::init_language_settings();
+    editor::init_editor_settings();
     color_scheme::init_color_scheme_system();
     yew::Renderer::<App>::new().render();
 }
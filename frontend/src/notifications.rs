@@ -10,11 +10,20 @@ pub enum NotificationType {
     Warning,
 }
 
+/// Button shown below the notification content, e.g. "Undo".
+#[derive(PartialEq, Clone)]
+pub struct NotificationAction {
+    pub label: String,
+    pub callback: Callback<()>,
+}
+
 #[derive(Properties, PartialEq, Clone)]
 pub struct NotificationProps {
     notification_type: NotificationType,
     notification_content: String,
     request_close_cb: Callback<()>,
+    #[prop_or_default]
+    action: Option<NotificationAction>,
 }
 
 const PROGRESS_MILISECONDS_TIMEOUT: u32 = 5000;
@@ -26,6 +35,7 @@ fn notification(props: &NotificationProps) -> Html {
         notification_type,
         notification_content,
         request_close_cb,
+        action,
     } = props;
 
     let progress_changed = use_force_update();
@@ -62,6 +72,21 @@ fn notification(props: &NotificationProps) -> Html {
         })
     };
 
+    let action_button = match action {
+        Some(NotificationAction { label, callback }) => {
+            let onclick = {
+                let callback = callback.clone();
+                let onclick = onclick.clone();
+                Callback::from(move |event: MouseEvent| {
+                    callback.emit(());
+                    onclick.emit(event);
+                })
+            };
+            html!(<button class="button is-small is-light mb-2" {onclick}>{ label }</button>)
+        }
+        None => html!(),
+    };
+
     let value = progress_miliseconds.to_string();
     let max = PROGRESS_MILISECONDS_TIMEOUT.to_string();
     let class = match notification_type {
@@ -82,6 +107,7 @@ fn notification(props: &NotificationProps) -> Html {
             <div class="content notification-content">
                 { notification_content }
             </div>
+            { action_button }
             <progress class="progress" {value} {max}></progress>
         </div>
     }
@@ -99,6 +125,7 @@ impl NotificationManager {
         &self,
         notification_content: String,
         notification_type: NotificationType,
+        action: Option<NotificationAction>,
     ) {
         let notification_id = *self.notifications_counter;
         let request_close_cb = {
@@ -114,7 +141,8 @@ impl NotificationManager {
             <Notification
                 {notification_type}
                 {notification_content}
-                {request_close_cb}/>
+                {request_close_cb}
+                {action}/>
         );
         let mut new_notifications = (*self.notifications).clone();
         new_notifications.insert(notification_id, notification);
@@ -127,7 +155,22 @@ impl NotificationManager {
         notification_content: Msg,
         notification_type: NotificationType,
     ) {
-        self.publish_notification(notification_content.into(), notification_type);
+        self.publish_notification(notification_content.into(), notification_type, None);
+    }
+
+    /// Publishes notification with a button, `callback` is called when it is clicked.
+    pub fn notify_with_action<Msg: Into<String>, Label: Into<String>>(
+        &self,
+        notification_content: Msg,
+        notification_type: NotificationType,
+        label: Label,
+        callback: Callback<()>,
+    ) {
+        let action = NotificationAction {
+            label: label.into(),
+            callback,
+        };
+        self.publish_notification(notification_content.into(), notification_type, Some(action));
     }
 
     pub fn notify_info<Msg: Into<String>>(&self, notification_content: Msg) {
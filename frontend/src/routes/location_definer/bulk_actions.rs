@@ -0,0 +1,211 @@
+/*
+Copyright (C) 2025 Mateusz Mazur (Mazurel) <mateusz.mazur@e.email>
+
+This program is free software; you can redistribute it and/or
+modify it under the terms of the GNU General Public License
+as published by the Free Software Foundation; either version 2
+of the License, or (at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program; if not, see
+<https://www.gnu.org/licenses/>.
+*/
+
+use libsopa::bulk::{BulkAction, FieldValue};
+use libsopa::localized::current_content_locale;
+use libsopa::query::Query;
+use libsopa::tags::{get_all_supported_tags_in_order, Tag};
+use web_sys::{HtmlInputElement, HtmlSelectElement};
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq)]
+pub struct BulkActionsToolbarProps {
+    pub selected_count: usize,
+    pub on_select_matching: Callback<Query>,
+    pub on_selection_cleared: Callback<()>,
+    pub on_action: Callback<BulkAction>,
+}
+
+fn tag_options(selected_tag_name: &str) -> Html {
+    get_all_supported_tags_in_order()
+        .into_iter()
+        .map(|tag| {
+            html!(
+                <option value={tag.name.clone()} selected={tag.name == selected_tag_name}>
+                    {tag.human_readable()}
+                </option>
+            )
+        })
+        .collect()
+}
+
+fn on_tag_changed(tag_name_state: &UseStateHandle<String>) -> Callback<Event> {
+    let tag_name_state = tag_name_state.clone();
+    Callback::from(move |event: Event| {
+        if let Some(select) = event.target_dyn_into::<HtmlSelectElement>() {
+            tag_name_state.set(select.value());
+        }
+    })
+}
+
+/// Selects locations matching a query and applies actions to all selected ones.
+#[function_component(BulkActionsToolbar)]
+pub fn bulk_actions_toolbar(props: &BulkActionsToolbarProps) -> Html {
+    let first_tag_name = || {
+        get_all_supported_tags_in_order()
+            .first()
+            .map(|tag| tag.name.clone())
+            .unwrap_or_default()
+    };
+    let query_state = use_state_eq(String::new);
+    let query_error_state: UseStateHandle<Option<String>> = use_state_eq(|| None);
+    let tag_name_state = use_state_eq(first_tag_name);
+    let replacement_tag_name_state = use_state_eq(first_tag_name);
+
+    let on_query_changed = {
+        let query_state = query_state.clone();
+        Callback::from(move |event: InputEvent| {
+            if let Some(input) = event.target_dyn_into::<HtmlInputElement>() {
+                query_state.set(input.value());
+            }
+        })
+    };
+
+    let on_select_matching_click = {
+        let query_state = query_state.clone();
+        let query_error_state = query_error_state.clone();
+        let on_select_matching = props.on_select_matching.clone();
+        Callback::from(move |_: MouseEvent| match query_state.parse::<Query>() {
+            Ok(query) => {
+                query_error_state.set(None);
+                on_select_matching.emit(query);
+            }
+            Err(err) => query_error_state.set(Some(err.to_string())),
+        })
+    };
+
+    let on_clear_click = props.on_selection_cleared.reform(|_: MouseEvent| ());
+
+    let tag_action_click = |to_action: fn(Tag, Tag) -> BulkAction| {
+        let tag_name_state = tag_name_state.clone();
+        let replacement_tag_name_state = replacement_tag_name_state.clone();
+        props.on_action.reform(move |_: MouseEvent| {
+            to_action(
+                Tag::new((*tag_name_state).clone()),
+                Tag::new((*replacement_tag_name_state).clone()),
+            )
+        })
+    };
+    let on_add_tag_click = tag_action_click(|tag, _| BulkAction::AddTag(tag));
+    let on_remove_tag_click = tag_action_click(|tag, _| BulkAction::RemoveTag(tag));
+    let on_replace_tag_click = tag_action_click(|old, new| BulkAction::ReplaceTag { old, new });
+
+    let on_set_description_click = {
+        let on_action = props.on_action.clone();
+        Callback::from(move |_: MouseEvent| {
+            let locale = current_content_locale();
+            let prompt = t!("location-definer-bulk-description-prompt", locale = locale);
+            if let Some(text) = gloo::dialogs::prompt(&prompt, None) {
+                on_action.emit(BulkAction::SetField(FieldValue::Description {
+                    locale: locale.to_string(),
+                    text,
+                }));
+            }
+        })
+    };
+
+    let on_delete_click = {
+        let on_action = props.on_action.clone();
+        let selected_count = props.selected_count;
+        Callback::from(move |_: MouseEvent| {
+            let confirm_delete = t!(
+                "location-definer-bulk-confirm-delete",
+                count = selected_count
+            );
+            if gloo::dialogs::confirm(&confirm_delete) {
+                on_action.emit(BulkAction::Delete);
+            }
+        })
+    };
+
+    let query_help = match &*query_error_state {
+        Some(error) => html!(<p class="help is-danger">{error}</p>),
+        None => html!(),
+    };
+    let nothing_selected = props.selected_count == 0;
+
+    html!(
+        <div class="box location-definer-bulk-actions">
+            <div class="field has-addons">
+                <div class="control is-expanded">
+                    <input class="input is-small" type="text"
+                        placeholder={t!("location-definer-bulk-query-placeholder")}
+                        value={(*query_state).clone()}
+                        oninput={on_query_changed}/>
+                </div>
+                <div class="control">
+                    <button class="button is-small is-info" onclick={on_select_matching_click}>
+                        {t!("location-definer-bulk-select-matching")}
+                    </button>
+                </div>
+            </div>
+            {query_help}
+            <div class="field is-grouped is-grouped-multiline">
+                <div class="control">
+                    <span class="tag is-medium">
+                        {t!("location-definer-bulk-selected", count = props.selected_count)}
+                    </span>
+                </div>
+                <div class="control">
+                    <button class="button is-small" disabled={nothing_selected} onclick={on_clear_click}>
+                        {t!("location-definer-bulk-clear-selection")}
+                    </button>
+                </div>
+                <div class="control">
+                    <div class="select is-small">
+                        <select onchange={on_tag_changed(&tag_name_state)}>
+                            {tag_options(&tag_name_state)}
+                        </select>
+                    </div>
+                </div>
+                <div class="control">
+                    <button class="button is-small is-success" disabled={nothing_selected} onclick={on_add_tag_click}>
+                        {t!("location-definer-bulk-add-tag")}
+                    </button>
+                </div>
+                <div class="control">
+                    <button class="button is-small is-warning" disabled={nothing_selected} onclick={on_remove_tag_click}>
+                        {t!("location-definer-bulk-remove-tag")}
+                    </button>
+                </div>
+                <div class="control">
+                    <button class="button is-small is-link" disabled={nothing_selected} onclick={on_replace_tag_click}>
+                        {t!("location-definer-bulk-replace-tag")}
+                    </button>
+                </div>
+                <div class="control">
+                    <div class="select is-small">
+                        <select onchange={on_tag_changed(&replacement_tag_name_state)}>
+                            {tag_options(&replacement_tag_name_state)}
+                        </select>
+                    </div>
+                </div>
+                <div class="control">
+                    <button class="button is-small" disabled={nothing_selected} onclick={on_set_description_click}>
+                        {t!("location-definer-bulk-set-description")}
+                    </button>
+                </div>
+                <div class="control">
+                    <button class="button is-small is-danger" disabled={nothing_selected} onclick={on_delete_click}>
+                        {t!("location-definer-bulk-delete")}
+                    </button>
+                </div>
+            </div>
+        </div>
+    )
+}
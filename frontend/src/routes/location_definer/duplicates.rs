@@ -0,0 +1,332 @@
+/*
+Copyright (C) 2025 Mateusz Mazur (Mazurel) <mateusz.mazur@e.email>
+
+This program is free software; you can redistribute it and/or
+modify it under the terms of the GNU General Public License
+as published by the Free Software Foundation; either version 2
+of the License, or (at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program; if not, see
+<https://www.gnu.org/licenses/>.
+*/
+
+use libsopa::duplicates::{DuplicateCandidate, DuplicateReason, FieldSelection, MergeSide};
+use libsopa::locations::Location;
+use libsopa::organisations::Organisation;
+use libsopa::time::Day;
+use std::collections::HashMap;
+use uuid::Uuid;
+use yew::prelude::*;
+
+use crate::locations::LocationsDatabase;
+
+fn reason_to_string(reason: &DuplicateReason) -> String {
+    match reason {
+        DuplicateReason::SimilarName(similarity) => t!(
+            "location-definer-duplicates-similar-name",
+            percent = (similarity * 100.0).round()
+        )
+        .to_string(),
+        DuplicateReason::SimilarAddress(similarity) => t!(
+            "location-definer-duplicates-similar-address",
+            percent = (similarity * 100.0).round()
+        )
+        .to_string(),
+        DuplicateReason::SharedContact(contact) => t!(
+            "location-definer-duplicates-shared-contact",
+            contact = contact.value
+        )
+        .to_string(),
+    }
+}
+
+fn opened_hours_to_string(location: &Location) -> String {
+    Day::get_all_days_in_week()
+        .into_iter()
+        .filter_map(|day| {
+            let time_span = location.opened_hours.get_day_time_span(&day)?;
+            Some(format!(
+                "{} {}-{}",
+                day.to_display_name(),
+                time_span.from.to_time_string(),
+                time_span.to.to_time_string()
+            ))
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn accessibility_to_string(location: &Location) -> String {
+    location
+        .accessibility
+        .known_features()
+        .into_iter()
+        .map(|(feature, availability)| format!("{feature}: {availability}"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+type FieldSide = fn(&mut FieldSelection) -> &mut MergeSide;
+/// Displays the field of the location, organisations are given to show their names.
+type FieldDisplay = fn(&Location, &HashMap<Uuid, Organisation>) -> String;
+
+/// Merged fields, with their labels and how they are displayed.
+const MERGED_FIELDS: [(&str, FieldSide, FieldDisplay); 8] = [
+    (
+        "location-definer-title-label",
+        |selection| &mut selection.name,
+        |location, _| location.name.to_string(),
+    ),
+    (
+        "location-definer-description-label",
+        |selection| &mut selection.description,
+        |location, _| location.description.to_string(),
+    ),
+    (
+        "location-definer-address-label",
+        |selection| &mut selection.address,
+        |location, _| location.address.to_string(),
+    ),
+    (
+        "location-definer-coordinates-label",
+        |selection| &mut selection.coordinates,
+        |location, _| {
+            location
+                .coordinates
+                .map(|coordinates| coordinates.to_string())
+                .unwrap_or_default()
+        },
+    ),
+    (
+        "open-hours",
+        |selection| &mut selection.opened_hours,
+        |location, _| opened_hours_to_string(location),
+    ),
+    (
+        "location-definer-accessibility-label",
+        |selection| &mut selection.accessibility,
+        |location, _| accessibility_to_string(location),
+    ),
+    (
+        "location-definer-service-terms-label",
+        |selection| &mut selection.service_terms,
+        |location, _| location.service_terms.to_string(),
+    ),
+    (
+        "location-definer-organisation-label",
+        |selection| &mut selection.organisation,
+        |location, organisations| {
+            location
+                .organisation
+                .and_then(|id| organisations.get(&id))
+                .map(|organisation| organisation.name.to_string())
+                .unwrap_or_default()
+        },
+    ),
+];
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct DuplicatesViewProps {
+    pub locations_db: LocationsDatabase,
+    /// Emits kept location, removed location and fields chosen for the merged one.
+    pub on_merge_accepted: Callback<(Location, Location, FieldSelection)>,
+    pub on_closed: Callback<()>,
+}
+
+/// Modal listing possible duplicates, with a field-by-field merge of the chosen pair.
+#[function_component(DuplicatesView)]
+pub fn duplicates_view(props: &DuplicatesViewProps) -> Html {
+    let chosen_candidate_state: UseStateHandle<Option<usize>> = use_state_eq(|| None);
+    let swapped_state = use_state_eq(|| false);
+    let selection_state = use_state_eq(FieldSelection::default);
+
+    let mut candidates: Vec<(DuplicateCandidate, Location, Location)> = vec![];
+    let mut organisations = HashMap::new();
+    props.locations_db.use_locations(|locations| {
+        organisations = locations.organisations.clone();
+        candidates = locations
+            .find_duplicates()
+            .into_iter()
+            .filter_map(|candidate| {
+                let first = locations.locations.get(&candidate.first)?.clone();
+                let second = locations.locations.get(&candidate.second)?.clone();
+                Some((candidate, first, second))
+            })
+            .collect();
+    });
+
+    let candidates_list = candidates
+        .iter()
+        .enumerate()
+        .map(|(idx, (candidate, first, second))| {
+            let onclick = {
+                let chosen_candidate_state = chosen_candidate_state.clone();
+                let swapped_state = swapped_state.clone();
+                let selection_state = selection_state.clone();
+                Callback::from(move |_: MouseEvent| {
+                    chosen_candidate_state.set(Some(idx));
+                    swapped_state.set(false);
+                    selection_state.set(FieldSelection::default());
+                })
+            };
+            let reasons = candidate
+                .reasons
+                .iter()
+                .map(reason_to_string)
+                .collect::<Vec<_>>()
+                .join(", ");
+            let class = classes!(
+                "panel-block",
+                "is-clickable",
+                (*chosen_candidate_state == Some(idx)).then_some("is-active")
+            );
+            html!(
+                <a {class} {onclick}>
+                    <span class="tag is-warning is-light mr-2">
+                        {format!("{:.0}%", candidate.score * 100.0)}
+                    </span>
+                    <span>
+                        <strong>{format!("{} / {}", first.name, second.name)}</strong>
+                        <br/>
+                        <small>{reasons}</small>
+                    </span>
+                </a>
+            )
+        })
+        .collect::<Html>();
+
+    let chosen = (*chosen_candidate_state)
+        .and_then(|idx| candidates.get(idx))
+        .map(|(_, first, second)| match *swapped_state {
+            false => (first.clone(), second.clone()),
+            true => (second.clone(), first.clone()),
+        });
+
+    let merge_form = match chosen {
+        None => html!(<p>{t!("location-definer-duplicates-choose")}</p>),
+        Some((kept, removed)) => {
+            let rows = MERGED_FIELDS
+                .iter()
+                .map(|(label_key, field, display)| {
+                    let mut selection = (*selection_state).clone();
+                    let side = *field(&mut selection);
+                    let choose = |chosen_side: MergeSide| {
+                        let selection_state = selection_state.clone();
+                        let mut selection = selection.clone();
+                        *field(&mut selection) = chosen_side;
+                        Callback::from(move |_: Event| selection_state.set(selection.clone()))
+                    };
+                    let (kept_value, removed_value) = (
+                        display(&kept, &organisations),
+                        display(&removed, &organisations),
+                    );
+                    let is_same = kept_value == removed_value;
+                    html!(
+                        <tr>
+                            <th>{t!(*label_key)}</th>
+                            <td>
+                                <label class="radio">
+                                    <input type="radio" checked={side == MergeSide::Kept}
+                                        onchange={choose(MergeSide::Kept)} disabled={is_same}/>
+                                    {" "}{kept_value}
+                                </label>
+                            </td>
+                            <td>
+                                <label class="radio">
+                                    <input type="radio" checked={side == MergeSide::Removed}
+                                        onchange={choose(MergeSide::Removed)} disabled={is_same}/>
+                                    {" "}{removed_value}
+                                </label>
+                            </td>
+                        </tr>
+                    )
+                })
+                .collect::<Html>();
+
+            let on_swap = {
+                let swapped_state = swapped_state.clone();
+                let selection_state = selection_state.clone();
+                Callback::from(move |_: MouseEvent| {
+                    swapped_state.set(!*swapped_state);
+                    selection_state.set(FieldSelection::default());
+                })
+            };
+            let on_merge = {
+                let on_merge_accepted = props.on_merge_accepted.clone();
+                let chosen_candidate_state = chosen_candidate_state.clone();
+                let selection = (*selection_state).clone();
+                let (kept, removed) = (kept.clone(), removed.clone());
+                Callback::from(move |_: MouseEvent| {
+                    let confirm_merge = t!(
+                        "location-definer-duplicates-confirm-merge",
+                        removed = removed.name,
+                        kept = kept.name
+                    );
+                    if gloo::dialogs::confirm(&confirm_merge) {
+                        on_merge_accepted.emit((kept.clone(), removed.clone(), selection.clone()));
+                        chosen_candidate_state.set(None);
+                    }
+                })
+            };
+
+            html!(
+                <>
+                    <table class="table is-fullwidth">
+                        <thead>
+                            <tr>
+                                <th></th>
+                                <th>{t!("location-definer-duplicates-kept")}</th>
+                                <th>{t!("location-definer-duplicates-removed")}</th>
+                            </tr>
+                        </thead>
+                        <tbody>{rows}</tbody>
+                    </table>
+                    <p class="help mb-3">{t!("location-definer-duplicates-joined-fields")}</p>
+                    <div class="buttons">
+                        <button class="button" onclick={on_swap}>
+                            {t!("location-definer-duplicates-swap")}
+                        </button>
+                        <button class="button is-primary" onclick={on_merge}>
+                            {t!("location-definer-duplicates-merge")}
+                        </button>
+                    </div>
+                </>
+            )
+        }
+    };
+
+    let on_close = {
+        let on_closed = props.on_closed.clone();
+        Callback::from(move |_: MouseEvent| on_closed.emit(()))
+    };
+
+    html!(
+        <div class="modal is-active">
+            <div class="modal-background"></div>
+            <div class="modal-card" style="width: 80%;">
+                <header class="modal-card-head">
+                    <p class="modal-card-title">{t!("location-definer-duplicates-title")}</p>
+                </header>
+                <section class="modal-card-body">
+                    if candidates.is_empty() {
+                        <p>{t!("location-definer-duplicates-none")}</p>
+                    } else {
+                        <div class="panel mb-4">{candidates_list}</div>
+                        {merge_form}
+                    }
+                </section>
+                <footer class="modal-card-foot buttons">
+                    <button class="button" onclick={on_close}>
+                        {t!("location-definer-duplicates-close")}
+                    </button>
+                </footer>
+            </div>
+        </div>
+    )
+}
@@ -18,12 +18,24 @@ along with this program; if not, see
 
 use super::location_edit_manager::LocationEditManager;
 use super::tags_selection::TagsSelectionEditForLocation;
+use crate::yew_components::accessibility::AccessibilityEdit;
+use crate::yew_components::address::AddressEdit;
 use crate::yew_components::opened_hours::OpenedHoursEdit;
+use crate::yew_components::service_terms::ServiceTermsEdit;
+use crate::yew_components::validation::{issues_of, ValidationMessages};
 use crate::yew_components::ContactMethodsEdit;
+use libsopa::accessibility::Accessibility;
+use libsopa::address::Address;
 use libsopa::contact::ContactMethods;
+use libsopa::geo::GeoPoint;
+use libsopa::localized::{current_content_locale, CONTENT_LOCALES};
 use libsopa::locations::Location;
+use libsopa::organisations::Organisation;
+use libsopa::publication::PublicationState;
+use libsopa::service_terms::ServiceTerms;
 use libsopa::time::OpenedHours;
-use web_sys::{HtmlInputElement, HtmlTextAreaElement};
+use libsopa::validation::FieldPath;
+use web_sys::{HtmlInputElement, HtmlSelectElement, HtmlTextAreaElement};
 use yew::prelude::*;
 
 fn edit_control_buttons(
@@ -52,36 +64,108 @@ fn edit_control_buttons(
     )
 }
 
+fn publication_buttons(location: &Location, location_edit_manager: &LocationEditManager) -> Html {
+    location
+        .publication_state()
+        .allowed_transitions()
+        .iter()
+        .map(|next_state| {
+            let (label, class) = match next_state {
+                PublicationState::Draft => (t!("location-definer-return-to-draft"), "is-light"),
+                PublicationState::InReview => (t!("location-definer-submit-for-review"), "is-info"),
+                PublicationState::Published => (t!("location-definer-publish"), "is-success"),
+                PublicationState::Archived => (t!("location-definer-archive"), "is-dark"),
+            };
+            let onclick = {
+                let location_edit_manager = location_edit_manager.clone();
+                let next_state = *next_state;
+                Callback::from(move |_: MouseEvent| {
+                    location_edit_manager.change_publication_state(next_state)
+                })
+            };
+            html!(
+                <button class={classes!("button", "is-small", "ml-2", class)} {onclick}>
+                    {label}
+                </button>
+            )
+        })
+        .collect()
+}
+
+/// Tabs choosing the language of the edited content,
+/// languages with missing translations are marked.
+fn content_locale_tabs(
+    location: &Location,
+    content_locale_state: &UseStateHandle<&'static str>,
+) -> Html {
+    let missing_locales: Vec<&str> = location
+        .name
+        .missing_locales()
+        .into_iter()
+        .chain(location.description.missing_locales())
+        .collect();
+    let tabs = CONTENT_LOCALES
+        .iter()
+        .map(|locale| {
+            let is_missing = missing_locales.contains(locale);
+            let onclick = {
+                let content_locale_state = content_locale_state.clone();
+                Callback::from(move |_: MouseEvent| content_locale_state.set(locale))
+            };
+            html!(
+                <li class={classes!((**content_locale_state == *locale).then_some("is-active"))}>
+                    <a {onclick}
+                        class={classes!(is_missing.then_some("has-text-warning-dark"))}
+                        title={is_missing.then(|| t!("location-definer-missing-translation").to_string())}>
+                        {locale.to_uppercase()}
+                        if is_missing {
+                            {" ⚠"}
+                        }
+                    </a>
+                </li>
+            )
+        })
+        .collect::<Html>();
+    html!(
+        <div class="tabs is-small container is-max-tablet">
+            <ul>{tabs}</ul>
+        </div>
+    )
+}
+
 #[derive(Properties, Clone, PartialEq)]
 pub struct LocationEditProps {
     #[prop_or(Location::default())]
     pub initial_location_to_edit: Location,
     pub location_edit_manager: LocationEditManager,
+    /// Organisations the location can be a branch of.
+    #[prop_or_default]
+    pub organisations: Vec<Organisation>,
 }
 
 #[function_component(LocationEdit)]
 pub fn location_edit(props: &LocationEditProps) -> Html {
+    let content_locale_state = use_state_eq(current_content_locale);
+    let content_locale: &str = *content_locale_state;
+
     let change_title: Callback<_> = {
         let location_edit_manager = props.location_edit_manager.clone();
         Callback::from(move |event: Event| {
             let maybe_input_element = event.target_dyn_into::<HtmlInputElement>();
             if let Some(input) = maybe_input_element {
                 let mut location = location_edit_manager.get_location_under_edit();
-                location.name = input.value();
+                location.name.set(content_locale, input.value());
                 location_edit_manager.stage_location_changes(location);
             }
         })
     };
 
-    let change_address: Callback<_> = {
+    let change_address: Callback<Address> = {
         let location_edit_manager = props.location_edit_manager.clone();
-        Callback::from(move |event: Event| {
-            let maybe_input_element = event.target_dyn_into::<HtmlInputElement>();
-            if let Some(input) = maybe_input_element {
-                let mut location = location_edit_manager.get_location_under_edit();
-                location.address = input.value();
-                location_edit_manager.stage_location_changes(location);
-            }
+        Callback::from(move |new_address| {
+            let mut location = location_edit_manager.get_location_under_edit();
+            location.address = new_address;
+            location_edit_manager.stage_location_changes(location);
         })
     };
 
@@ -91,12 +175,32 @@ pub fn location_edit(props: &LocationEditProps) -> Html {
             let maybe_text_area_element = event.target_dyn_into::<HtmlTextAreaElement>();
             if let Some(text_area) = maybe_text_area_element {
                 let mut location = location_edit_manager.get_location_under_edit();
-                location.description = text_area.value();
+                location.description.set(content_locale, text_area.value());
                 location_edit_manager.stage_location_changes(location);
             }
         })
     };
 
+    let coordinates_invalid = use_state(|| false);
+    let change_coordinates: Callback<_> = {
+        let location_edit_manager = props.location_edit_manager.clone();
+        let coordinates_invalid = coordinates_invalid.clone();
+        Callback::from(move |event: Event| {
+            let maybe_input_element = event.target_dyn_into::<HtmlInputElement>();
+            if let Some(input) = maybe_input_element {
+                let value = input.value();
+                let coordinates = GeoPoint::parse(&value);
+                let is_invalid = coordinates.is_none() && !value.trim().is_empty();
+                coordinates_invalid.set(is_invalid);
+                if !is_invalid {
+                    let mut location = location_edit_manager.get_location_under_edit();
+                    location.coordinates = coordinates;
+                    location_edit_manager.stage_location_changes(location);
+                }
+            }
+        })
+    };
+
     let change_contact_methods: Callback<ContactMethods> = {
         let location_edit_manager = props.location_edit_manager.clone();
         Callback::from(move |new_contact_methods| {
@@ -115,6 +219,44 @@ pub fn location_edit(props: &LocationEditProps) -> Html {
         })
     };
 
+    let change_accessibility: Callback<Accessibility> = {
+        let location_edit_manager = props.location_edit_manager.clone();
+        Callback::from(move |new_accessibility| {
+            let mut location = location_edit_manager.get_location_under_edit();
+            location.accessibility = new_accessibility;
+            location_edit_manager.stage_location_changes(location);
+        })
+    };
+
+    let change_service_terms: Callback<ServiceTerms> = {
+        let location_edit_manager = props.location_edit_manager.clone();
+        Callback::from(move |new_service_terms| {
+            let mut location = location_edit_manager.get_location_under_edit();
+            location.service_terms = new_service_terms;
+            location_edit_manager.stage_location_changes(location);
+        })
+    };
+
+    let change_organisation: Callback<_> = {
+        let location_edit_manager = props.location_edit_manager.clone();
+        Callback::from(move |event: Event| {
+            if let Some(select) = event.target_dyn_into::<HtmlSelectElement>() {
+                let mut location = location_edit_manager.get_location_under_edit();
+                location.organisation = select.value().parse().ok();
+                location_edit_manager.stage_location_changes(location);
+            }
+        })
+    };
+
+    let button_verified_on_click: Callback<MouseEvent> = {
+        let location_edit_manager = props.location_edit_manager.clone();
+        Callback::from(move |_| {
+            let mut location = location_edit_manager.get_location_under_edit();
+            location.mark_verified();
+            location_edit_manager.stage_location_changes(location);
+        })
+    };
+
     let button_save_on_click: Callback<MouseEvent> = {
         let location_edit_manager = props.location_edit_manager.clone();
         Callback::from(move |_| {
@@ -137,56 +279,144 @@ pub fn location_edit(props: &LocationEditProps) -> Html {
     };
 
     let location_to_edit = props.initial_location_to_edit.clone();
-    let description = location_to_edit.description.clone();
+    let organisation_options = props
+        .organisations
+        .iter()
+        .map(|organisation| {
+            let is_selected = location_to_edit.organisation == Some(organisation.get_id());
+            html!(
+                <option value={organisation.get_id().to_string()} selected={is_selected}>
+                    {organisation.name.to_string()}
+                </option>
+            )
+        })
+        .collect::<Html>();
+    let name = location_to_edit
+        .name
+        .translation(content_locale)
+        .unwrap_or_default();
+    let description = location_to_edit
+        .description
+        .translation(content_locale)
+        .unwrap_or_default()
+        .to_string();
+    // Missing translation shows the text it falls back to
+    let name_placeholder = match location_to_edit.name.is_empty() {
+        true => t!("location-definer-title-placeholder").to_string(),
+        false => location_to_edit.name.get(content_locale).to_string(),
+    };
+    let description_placeholder = match location_to_edit.description.is_empty() {
+        true => t!("location-definer-description-placeholder").to_string(),
+        false => location_to_edit.description.get(content_locale).to_string(),
+    };
+    let issues = location_to_edit.validate();
+    let unknown = || t!("location-definer-audit-unknown").to_string();
+    let audit_info = t!(
+        "location-definer-audit-info",
+        created = location_to_edit
+            .created_at
+            .map_or_else(unknown, |at| at.to_string()),
+        updated = location_to_edit
+            .updated_at
+            .map_or_else(unknown, |at| at.to_string()),
+        editor = match location_to_edit.updated_by.is_empty() {
+            true => unknown(),
+            false => location_to_edit.updated_by.clone(),
+        }
+    );
+    let verification_info = match location_to_edit.last_verified_at {
+        Some(verified_at) => t!("location-verified-on", date = verified_at),
+        None => t!("location-definer-never-verified"),
+    };
 
     html!(
         <>
             <div class="content">
                 <h2>{t!("location-definer-prompt")}</h2>
             </div>
+            {content_locale_tabs(&location_to_edit, &content_locale_state)}
+            <div class="field container is-max-tablet">
+                <div class="label">{t!("location-definer-organisation-label")}</div>
+                <div class="select is-fullwidth">
+                    <select onchange={change_organisation}>
+                        <option value="" selected={location_to_edit.organisation.is_none()}>
+                            {t!("location-definer-no-organisation")}
+                        </option>
+                        {organisation_options}
+                    </select>
+                </div>
+                if location_to_edit.organisation.is_some() {
+                    <p class="help">{t!("location-definer-organisations-inheritance-help")}</p>
+                }
+            </div>
             <div class="field container is-max-tablet">
                 <div class="label">{t!("location-definer-title-label")}</div>
                 <div class="control">
                     <input
                         class={"input"}
                         type={"text"}
-                        value={location_to_edit.name.clone()}
-                        placeholder={t!("location-definer-title-placeholder")}
+                        value={name.to_string()}
+                        placeholder={name_placeholder}
                         onchange={change_title}
                         />
                 </div>
+                <ValidationMessages issues={issues_of(&issues, &FieldPath::Name)}/>
             </div>
             <div class="field container is-max-tablet">
                 <div class="label">{t!("location-definer-description-label")}</div>
                 <div class="control">
                     <textarea
                         class="textarea"
-                        placeholder={t!("location-definer-description-placeholder")}
-                        value={description}
+                        placeholder={description_placeholder}
+                        value={description.clone()}
                         oninput={change_description}>
                         {description}
                     </textarea>
                 </div>
             </div>
+            <AddressEdit address={location_to_edit.address.clone()} on_address_changed={change_address} issues={issues.clone()}/>
             <div class="field container is-max-tablet">
-                <div class="label">{t!("location-definer-address-label")}</div>
+                <div class="label">{t!("location-definer-coordinates-label")}</div>
                 <div class="control">
                     <input
-                        class={"input"}
+                        class={classes!("input", coordinates_invalid.then_some("is-danger"))}
                         type={"text"}
-                        value={location_to_edit.address.clone()}
-                        placeholder={t!("location-definer-address-placeholder")}
-                        onchange={change_address}
+                        value={location_to_edit.coordinates.map(|c| c.to_string()).unwrap_or_default()}
+                        placeholder={t!("location-definer-coordinates-placeholder")}
+                        onchange={change_coordinates}
                         />
                 </div>
+                if *coordinates_invalid {
+                    <p class="help is-danger">{t!("location-definer-coordinates-invalid")}</p>
+                }
             </div>
-            <OpenedHoursEdit opened_hours={location_to_edit.opened_hours.clone()} on_opened_hours_changed={change_opened_hours}/>
-            <ContactMethodsEdit methods={location_to_edit.contact_methods.clone()} on_methods_changed={change_contact_methods}/>
+            <OpenedHoursEdit opened_hours={location_to_edit.opened_hours.clone()} on_opened_hours_changed={change_opened_hours} issues={issues.clone()}/>
+            <ContactMethodsEdit methods={location_to_edit.contact_methods.clone()} on_methods_changed={change_contact_methods} issues={issues.clone()}/>
+            <ServiceTermsEdit service_terms={location_to_edit.service_terms.clone()} on_service_terms_changed={change_service_terms} issues={issues.clone()}/>
+            <AccessibilityEdit accessibility={location_to_edit.accessibility.clone()} on_accessibility_changed={change_accessibility}/>
             <div class="field">
                 <div class="label">{t!("location-definer-tags-label")}</div>
                 <div class="control">
                     <TagsSelectionEditForLocation location={location_to_edit.clone()} location_edit_manager={props.location_edit_manager.clone()}/>
                 </div>
+                <ValidationMessages issues={issues_of(&issues, &FieldPath::Tags)}/>
+            </div>
+            <div class="field container is-max-tablet">
+                <div class="label">{t!("location-definer-publication-label")}</div>
+                <div class="is-flex is-align-items-center">
+                    <span class="tag is-medium">{location_to_edit.publication_state().to_string()}</span>
+                    {publication_buttons(&location_to_edit, &props.location_edit_manager)}
+                </div>
+            </div>
+            <div class="field container is-max-tablet">
+                <div class="label">{t!("location-definer-audit-label")}</div>
+                <p class="is-size-7">{audit_info}</p>
+                <div class="is-flex is-align-items-center mt-2">
+                    <span class="is-size-7">{verification_info}</span>
+                    <button class="button is-small is-success is-light ml-3" onclick={button_verified_on_click}>
+                        {t!("location-definer-mark-verified")}
+                    </button>
+                </div>
             </div>
             {edit_control_buttons(
                 button_save_on_click,
@@ -19,9 +19,66 @@ along with this program; if not, see
 use std::ops::Deref;
 
 use crate::app::SharedAppState;
-use libsopa::locations::Location;
+use crate::locations::LocationsDatabase;
+use crate::notifications::NotificationManager;
+use libsopa::error::Error;
+use libsopa::journal::JournaledLocations;
+use libsopa::locations::{Location, LocationUpdate};
+use libsopa::publication::PublicationState;
+use libsopa::validation::has_errors;
 use yew::prelude::*;
 
+/// Asks user whether `stale` changes should overwrite `current` location
+/// from the database. Returns location that should be edited from now on.
+fn resolve_conflict(
+    locations: &mut JournaledLocations,
+    stale: Location,
+    current: Location,
+) -> Location {
+    let confirm_overwrite = t!(
+        "location-definer-confirm-conflict-overwrite",
+        name = current.name
+    );
+    if gloo::dialogs::confirm(&confirm_overwrite) {
+        locations.force_update(stale)
+    } else {
+        current
+    }
+}
+
+/// Pushes `location` to the database, unless it has validation errors.
+fn commit_location(
+    locations_db: &LocationsDatabase,
+    notifications: &NotificationManager,
+    selected_location_state: &UseStateHandle<Location>,
+    location: Location,
+) {
+    let issues = location.validate();
+    if has_errors(&issues) {
+        let errors = issues
+            .iter()
+            .filter(|issue| issue.is_error())
+            .map(|issue| issue.kind.to_string())
+            .collect::<Vec<_>>()
+            .join("; ");
+        notifications.notify_error(t!("validation-errors-block-saving", issues = errors));
+        return;
+    }
+    let notifications = notifications.clone();
+    let selected_location_state = selected_location_state.clone();
+    locations_db.use_locations_mut_synced(move |locations| match locations.push_update(location) {
+        Ok(stored_location) => {
+            selected_location_state.set(stored_location);
+            notifications.notify_info(t!("location-definer-commited-changes"));
+        }
+        Err(Error::Conflict { stale, current }) => {
+            let resolved_location = resolve_conflict(locations, *stale, *current);
+            selected_location_state.set(resolved_location);
+        }
+        Err(err) => notifications.notify_error(err.to_string()),
+    });
+}
+
 /// Is a helper object that can be passed using properties
 /// and can be used to work with Location that is currently being edited.
 /// It offers two types of location saving:
@@ -31,6 +88,7 @@ use yew::prelude::*;
 pub struct LocationEditManager {
     stage_changes_cb: Callback<Location>,
     commit_changes_cb: Callback<()>,
+    change_publication_state_cb: Callback<PublicationState>,
     clear_changes_cb: Callback<()>,
     get_location_under_edit_cb: Callback<(), Location>,
     request_new_location_cb: Callback<(), Location>,
@@ -56,12 +114,10 @@ impl LocationEditManager {
                 let new_location_ref = &mut new_location;
                 locations_db.use_locations_mut(move |locations| {
                     let new_location_name = t!("new-location-name");
-                    let new_location_address = t!("new-location-address");
 
                     let new_location = new_location_ref;
                     *new_location = Some(locations.push_new(|location| {
-                        location.name = new_location_name.to_string();
-                        location.address = new_location_address.to_string();
+                        location.name.set(&rust_i18n::locale(), new_location_name);
                     }));
                 });
                 new_location.unwrap()
@@ -78,26 +134,48 @@ impl LocationEditManager {
         let commit_changes_cb = {
             let selected_location = selected_location.clone();
             let locations_db: UseStateHandle<_> = app_state.locations_db.clone();
+            let notifications = app_state.notifications.clone();
             Callback::from(move |_| {
-                let mut locations_db = locations_db.deref().clone();
-                let notifications = app_state.notifications.clone();
-                let selected_location = selected_location.clone().deref().clone();
-                locations_db.use_locations_mut(move |locations| {
-                    locations.push_update(selected_location);
-                    notifications.notify_info(t!("location-definer-commited-changes"));
-                });
+                commit_location(
+                    &locations_db,
+                    &notifications,
+                    &selected_location,
+                    selected_location.deref().clone(),
+                );
+            })
+        };
+
+        let change_publication_state_cb = {
+            let selected_location = selected_location.clone();
+            let locations_db: UseStateHandle<_> = app_state.locations_db.clone();
+            let notifications = app_state.notifications.clone();
+            Callback::from(move |next_state: PublicationState| {
+                let mut location = selected_location.deref().clone();
+                match location.transition_to(next_state) {
+                    Ok(()) => {
+                        commit_location(&locations_db, &notifications, &selected_location, location)
+                    }
+                    Err(err) => notifications.notify_error(err.to_string()),
+                }
             })
         };
 
         let clear_changes_cb = {
             let selected_location = selected_location.clone();
             let locations_db: UseStateHandle<_> = app_state.locations_db.clone();
+            let notifications = app_state.notifications.clone();
             Callback::from(move |_| {
                 let confirm_location_cleared = t!("location-definer-confirm-location-cleared");
                 if gloo::dialogs::confirm(&confirm_location_cleared) {
                     locations_db.use_locations(|locations| {
-                        if let Some(clean_location) = locations.fetch_update(&selected_location) {
-                            selected_location.set(clean_location);
+                        match locations.fetch_update(&selected_location) {
+                            LocationUpdate::Unchanged => (),
+                            LocationUpdate::Changed(clean_location) => {
+                                selected_location.set(clean_location);
+                            }
+                            LocationUpdate::Deleted => {
+                                notifications.notify_error(t!("error-missing-location"));
+                            }
                         }
                     });
                 }
@@ -118,6 +196,7 @@ impl LocationEditManager {
         LocationEditManager {
             stage_changes_cb: update_location_cb,
             commit_changes_cb,
+            change_publication_state_cb,
             clear_changes_cb,
             get_location_under_edit_cb,
             request_new_location_cb,
@@ -131,6 +210,12 @@ impl LocationEditManager {
         self.commit_changes_cb.emit(())
     }
 
+    /// Commits changes together with the publication state change,
+    /// see `Location::transition_to`.
+    pub fn change_publication_state(&self, next_state: PublicationState) {
+        self.change_publication_state_cb.emit(next_state)
+    }
+
     /// Update location changes, so that they are staged
     /// before being commited.
     pub fn stage_location_changes(&self, modified_location: Location) {
@@ -0,0 +1,169 @@
+/*
+Copyright (C) 2025 Mateusz Mazur (Mazurel) <mateusz.mazur@e.email>
+
+This program is free software; you can redistribute it and/or
+modify it under the terms of the GNU General Public License
+as published by the Free Software Foundation; either version 2
+of the License, or (at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program; if not, see
+<https://www.gnu.org/licenses/>.
+*/
+
+use libsopa::locations::{Location, Locations};
+use libsopa::merge::{MergeReport, MergeStrategy};
+use web_sys::HtmlSelectElement;
+use yew::prelude::*;
+
+use crate::locations::LocationsDatabase;
+
+/// Section listing names of the locations or organisations, skipped when empty.
+fn merge_report_section(title: String, names: Vec<String>) -> Html {
+    if names.is_empty() {
+        return html!();
+    }
+
+    html!(
+        <div class="block">
+            <div class="label">{format!("{title} ({})", names.len())}</div>
+            <ul>
+                {
+                    names
+                        .into_iter()
+                        .map(|name| html!(<li>{name}</li>))
+                        .collect::<Html>()
+                }
+            </ul>
+        </div>
+    )
+}
+
+fn location_names<'a>(locations: impl IntoIterator<Item = &'a Location>) -> Vec<String> {
+    locations
+        .into_iter()
+        .map(|location| location.name.to_string())
+        .collect()
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct MergePreviewProps {
+    pub locations_db: LocationsDatabase,
+    pub their_locations: Locations,
+    pub on_merge_accepted: Callback<MergeStrategy>,
+    pub on_merge_cancelled: Callback<()>,
+}
+
+/// Modal presenting `MergeReport` and changelog of merging `their_locations`
+/// into the database, before anything is commited.
+#[function_component(MergePreview)]
+pub fn merge_preview(props: &MergePreviewProps) -> Html {
+    let strategy_state = use_state_eq(MergeStrategy::default);
+
+    let (report, changelog): (MergeReport, String) = {
+        let mut report = MergeReport::default();
+        let mut changelog = String::new();
+        props.locations_db.use_locations(|locations| {
+            let mut merged_locations = locations.clone();
+            report = merged_locations.merge(&props.their_locations, *strategy_state);
+            changelog = locations.diff(&merged_locations).to_string();
+        });
+        (report, changelog)
+    };
+
+    let on_strategy_changed = {
+        let strategy_state = strategy_state.clone();
+        Callback::from(move |event: Event| {
+            if let Some(select) = event.target_dyn_into::<HtmlSelectElement>() {
+                match select.value().as_str() {
+                    "theirs" => strategy_state.set(MergeStrategy::TakeTheirs),
+                    _ => strategy_state.set(MergeStrategy::KeepOurs),
+                }
+            }
+        })
+    };
+
+    let on_accept = {
+        let on_merge_accepted = props.on_merge_accepted.clone();
+        let strategy = *strategy_state;
+        Callback::from(move |_: MouseEvent| on_merge_accepted.emit(strategy))
+    };
+
+    let on_cancel = {
+        let on_merge_cancelled = props.on_merge_cancelled.clone();
+        Callback::from(move |_: MouseEvent| on_merge_cancelled.emit(()))
+    };
+
+    let conflicts = location_names(
+        report
+            .conflicts
+            .iter()
+            .map(|conflict| conflict.resolve(report.strategy)),
+    );
+    let added_organisations: Vec<String> = report
+        .added_organisations
+        .iter()
+        .map(|organisation| organisation.name.to_string())
+        .collect();
+    let organisation_conflicts: Vec<String> = report
+        .organisation_conflicts
+        .iter()
+        .map(|conflict| conflict.resolve(report.strategy).name.to_string())
+        .collect();
+
+    html!(
+        <div class="modal is-active">
+            <div class="modal-background"></div>
+            <div class="modal-card">
+                <header class="modal-card-head">
+                    <p class="modal-card-title">{t!("location-definer-merge-title")}</p>
+                </header>
+                <section class="modal-card-body">
+                    <div class="field">
+                        <div class="label">{t!("location-definer-merge-strategy-label")}</div>
+                        <div class="control">
+                            <div class="select">
+                                <select onchange={on_strategy_changed}>
+                                    <option value="ours" selected={report.strategy == MergeStrategy::KeepOurs}>
+                                        {t!("location-definer-merge-strategy-ours")}
+                                    </option>
+                                    <option value="theirs" selected={report.strategy == MergeStrategy::TakeTheirs}>
+                                        {t!("location-definer-merge-strategy-theirs")}
+                                    </option>
+                                </select>
+                            </div>
+                        </div>
+                    </div>
+                    {merge_report_section(t!("location-definer-merge-added").to_string(), location_names(&report.added))}
+                    {merge_report_section(t!("location-definer-merge-updated").to_string(), location_names(&report.updated))}
+                    {merge_report_section(t!("location-definer-merge-conflicts").to_string(), conflicts)}
+                    {merge_report_section(t!("location-definer-merge-outdated").to_string(), location_names(&report.outdated))}
+                    {merge_report_section(t!("location-definer-merge-added-organisations").to_string(), added_organisations)}
+                    {merge_report_section(t!("location-definer-merge-organisation-conflicts").to_string(), organisation_conflicts)}
+                    <div class="block">
+                        {format!("{}: {}", t!("location-definer-merge-unchanged"), report.unchanged)}
+                    </div>
+                    if !changelog.is_empty() {
+                        <div class="block">
+                            <div class="label">{t!("location-definer-merge-changelog")}</div>
+                            <pre>{changelog}</pre>
+                        </div>
+                    }
+                </section>
+                <footer class="modal-card-foot buttons">
+                    <button class="button is-primary" disabled={report.is_noop()} onclick={on_accept}>
+                        {t!("location-definer-merge-accept")}
+                    </button>
+                    <button class="button" onclick={on_cancel}>
+                        {t!("location-definer-merge-cancel")}
+                    </button>
+                </footer>
+            </div>
+        </div>
+    )
+}
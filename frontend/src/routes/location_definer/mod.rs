@@ -16,46 +16,97 @@ along with this program; if not, see
 <https://www.gnu.org/licenses/>.
 */
 
+use std::collections::HashSet;
 use std::ops::Deref;
 
+use crate::notifications::NotificationType;
+use crate::routes::location_finder::current_query_context;
 use crate::yew_components::{LocationView, SelectionSettings};
-use libsopa::locations::Location;
+use bulk_actions::BulkActionsToolbar;
+use duplicates::DuplicatesView;
+use libsopa::audit;
+use libsopa::bulk::{BulkAction, BulkTarget};
+use libsopa::duplicates::FieldSelection;
+use libsopa::journal::Operation;
+use libsopa::locations::{Location, Locations};
+use libsopa::merge::MergeStrategy;
+use libsopa::organisations::Organisation;
+use libsopa::publication::PublicationState;
+use libsopa::query::Query;
 use log::info;
+use uuid::Uuid;
+use web_sys::{HtmlInputElement, HtmlSelectElement};
 use yew::prelude::*;
 
 use crate::app::SharedAppState;
 use crate::download::{download_binary_data, upload_binary_data};
+use crate::editor::set_editor_name;
 use location_edit::LocationEdit;
 use location_edit_manager::LocationEditManager;
+use merge_preview::MergePreview;
+use organisations::OrganisationsView;
+use trash::TrashView;
 
+mod bulk_actions;
+mod duplicates;
 mod location_edit;
 mod location_edit_manager;
+mod merge_preview;
+mod organisations;
 mod tags_selection;
+mod trash;
 
 #[derive(Properties, Clone, PartialEq)]
 pub struct LocationDefinerProps {
     pub app_state: SharedAppState,
 }
 
+/// Locations in the trash are only listed in the `TrashView`.
 fn fetch_all_locations(db: &crate::locations::LocationsDatabase) -> Vec<Location> {
     let mut locs = Vec::new();
     db.use_locations(|locations| {
         locs.append(&mut locations.locations_in_random_order());
     });
+    locs.retain(|location| !location.is_deleted());
     locs
 }
 
+/// Refetches locations after the whole database changed (e.g. undo),
+/// keeping the same location selected if it still exists.
+fn reselect_location(
+    db: &crate::locations::LocationsDatabase,
+    locations_list: &UseStateHandle<Vec<Location>>,
+    selected_location_index_state: &UseStateHandle<usize>,
+    selected_location_state: &UseStateHandle<Location>,
+) {
+    let locations = fetch_all_locations(db);
+    let selected_id = selected_location_state.get_id();
+    let new_idx = locations
+        .iter()
+        .position(|location| location.get_id() == selected_id)
+        .unwrap_or(0);
+    if let Some(location) = locations.get(new_idx) {
+        selected_location_index_state.set(new_idx);
+        selected_location_state.set(location.clone());
+    }
+    locations_list.set(locations);
+}
+
 #[function_component(LocationDefiner)]
 pub fn location_definer(props: &LocationDefinerProps) -> Html {
     let location_definer_add_label = t!("location-definer-add-label");
     let location_definer_save_label = t!("location-definer-save-label");
     let location_definer_load_label = t!("location-definer-load-label");
+    let location_definer_merge_label = t!("location-definer-merge-label");
+    let location_definer_undo_label = t!("location-definer-undo-label");
+    let location_definer_redo_label = t!("location-definer-redo-label");
+    let location_definer_duplicates_label = t!("location-definer-duplicates-label");
 
     let locations_list = {
         let locations_db = props.app_state.locations_db.clone();
         use_state(|| fetch_all_locations(&locations_db))
     };
-    let selected_location_index_state = use_state(|| 0);
+    let selected_location_index_state: UseStateHandle<usize> = use_state(|| 0);
     let selected_location_state = {
         let locations = locations_list.clone();
         let selected_location_index_state = selected_location_index_state.clone();
@@ -104,24 +155,105 @@ pub fn location_definer(props: &LocationDefinerProps) -> Html {
         );
     }
 
+    let on_undo_request = {
+        let locations_db = props.app_state.locations_db.clone();
+        let notifications = props.app_state.notifications.clone();
+        let locations_list = locations_list.clone();
+        let selected_location_state = selected_location_state.clone();
+        let selected_location_index_state = selected_location_index_state.clone();
+        Callback::from(move |_: ()| {
+            let mut locations = (*locations_db).clone();
+            if locations.undo().is_none() {
+                notifications.notify_info(t!("location-definer-nothing-to-undo"));
+                return;
+            }
+            reselect_location(
+                &locations,
+                &locations_list,
+                &selected_location_index_state,
+                &selected_location_state,
+            );
+            locations_db.set(locations);
+        })
+    };
+
+    let on_redo_request = {
+        let locations_db = props.app_state.locations_db.clone();
+        let notifications = props.app_state.notifications.clone();
+        let locations_list = locations_list.clone();
+        let selected_location_state = selected_location_state.clone();
+        let selected_location_index_state = selected_location_index_state.clone();
+        Callback::from(move |_: ()| {
+            let mut locations = (*locations_db).clone();
+            if locations.redo().is_none() {
+                notifications.notify_info(t!("location-definer-nothing-to-redo"));
+                return;
+            }
+            reselect_location(
+                &locations,
+                &locations_list,
+                &selected_location_index_state,
+                &selected_location_state,
+            );
+            locations_db.set(locations);
+        })
+    };
+
     let on_current_location_removed = {
+        let on_undo_request = on_undo_request.clone();
         let locations_db = props.app_state.locations_db.clone();
+        let notifications = props.app_state.notifications.clone();
         let locations_list = locations_list.clone();
         let selected_location_state = selected_location_state.clone();
         let selected_location_index_state = selected_location_index_state.clone();
         Callback::from(move |_| {
             let locations_db = locations_db.clone();
+            let notifications = notifications.clone();
             let mut locations = (*locations_db).clone();
             let locations_list = locations_list.clone();
-            let new_idx = match *selected_location_index_state <= 0 {
-                false => *selected_location_index_state + 1,
-                true => 0,
+            let last_idx = locations_list.len().saturating_sub(1);
+            let new_idx = match *selected_location_index_state {
+                0 => 0,
+                idx => (idx + 1).min(last_idx),
             };
             let selected_location = (*selected_location_state).clone();
-            selected_location_index_state.set(new_idx);
-            selected_location_state.set(locations_list[new_idx].clone());
+            if let Some(new_location) = locations_list.get(new_idx) {
+                selected_location_index_state.set(new_idx);
+                selected_location_state.set(new_location.clone());
+            }
+            let undo_removal_cb = {
+                let locations = locations.clone();
+                let on_undo_request = on_undo_request.clone();
+                let removed_id = selected_location.get_id();
+                // Journal may have changed since the notification was shown,
+                // so only the removal itself can be undone from here.
+                Callback::from(move |_| {
+                    let mut is_last_operation = false;
+                    locations.use_journal(|journal| {
+                        is_last_operation = matches!(
+                            journal.last_done(),
+                            Some(Operation::PushUpdate { after, .. })
+                                if after.get_id() == removed_id && after.is_deleted()
+                        );
+                    });
+                    if is_last_operation {
+                        on_undo_request.emit(());
+                    }
+                })
+            };
             locations.use_locations_mut(move |locations| {
-                locations.remove(selected_location);
+                match locations.move_to_trash(&selected_location) {
+                    Ok(removed_location) => notifications.notify_with_action(
+                        t!(
+                            "location-definer-location-removed",
+                            name = removed_location.name
+                        ),
+                        NotificationType::Info,
+                        t!("location-definer-undo-label"),
+                        undo_removal_cb,
+                    ),
+                    Err(err) => notifications.notify_error(err.to_string()),
+                }
             });
             locations_db.set(locations);
         })
@@ -155,9 +287,15 @@ pub fn location_definer(props: &LocationDefinerProps) -> Html {
         let notifications = props.app_state.notifications.clone();
         Callback::from(move |_: MouseEvent| {
             let notifications = notifications.clone();
-            let mut locations_db = locations_db.deref().clone();
-            locations_db.use_locations_mut(|locations| {
-                let binary_db = locations.to_bin_data();
+            let locations_db = locations_db.deref().clone();
+            locations_db.use_locations(|locations| {
+                let binary_db = match locations.to_bin_data() {
+                    Ok(binary_db) => binary_db,
+                    Err(err) => {
+                        notifications.notify_error(format!("{}: {}", t!("download-error"), err));
+                        return;
+                    }
+                };
                 let binary_db_reference: &[u8] = &binary_db;
                 match download_binary_data(binary_db_reference, "sopa.bson", "application/bson") {
                     Ok(()) => notifications.notify_info(t!("download-ok")),
@@ -171,22 +309,360 @@ pub fn location_definer(props: &LocationDefinerProps) -> Html {
 
     let on_db_load_request_cb = {
         let locations_db = props.app_state.locations_db.clone();
+        let notifications = props.app_state.notifications.clone();
         Callback::from(move |_: MouseEvent| {
             let locations_db = locations_db.clone();
-            // TODO: Add notification when notification system will be ready
+            let notifications = notifications.clone();
             upload_binary_data(
                 "sopa.bson",
                 "*.bson",
                 Callback::from(move |binary_data| {
                     let locations_db_new = (*locations_db).clone();
-                    locations_db_new.reload_database_from_bin(binary_data);
-                    locations_db.set(locations_db_new);
+                    match locations_db_new.reload_database_from_bin(binary_data) {
+                        Ok(()) => {
+                            notifications.notify_info(t!("location-definer-database-loaded"));
+                            locations_db.set(locations_db_new);
+                        }
+                        Err(err) => notifications.notify_error(format!(
+                            "{}: {}",
+                            t!("location-definer-database-load-failed"),
+                            err
+                        )),
+                    }
                 }),
             )
             .expect("Upload should succeed");
         })
     };
 
+    let merge_candidate_state: UseStateHandle<Option<Locations>> = use_state(|| None);
+
+    let on_db_merge_request_cb = {
+        let notifications = props.app_state.notifications.clone();
+        let merge_candidate_state = merge_candidate_state.clone();
+        Callback::from(move |_: MouseEvent| {
+            let notifications = notifications.clone();
+            let merge_candidate_state = merge_candidate_state.clone();
+            upload_binary_data(
+                "sopa.bson",
+                "*.bson",
+                Callback::from(
+                    move |binary_data| match Locations::from_bin_data(binary_data) {
+                        Ok(their_locations) => merge_candidate_state.set(Some(their_locations)),
+                        Err(err) => notifications.notify_error(format!(
+                            "{}: {}",
+                            t!("location-definer-database-load-failed"),
+                            err
+                        )),
+                    },
+                ),
+            )
+            .expect("Upload should succeed");
+        })
+    };
+
+    let merge_preview = match (*merge_candidate_state).clone() {
+        None => html!(),
+        Some(their_locations) => {
+            let on_merge_accepted = {
+                let locations_db = props.app_state.locations_db.clone();
+                let notifications = props.app_state.notifications.clone();
+                let merge_candidate_state = merge_candidate_state.clone();
+                let their_locations = their_locations.clone();
+                Callback::from(move |strategy: MergeStrategy| {
+                    let mut locations = (*locations_db).clone();
+                    let notifications = notifications.clone();
+                    let their_locations = their_locations.clone();
+                    locations.use_locations_mut(move |locations| {
+                        locations.merge(&their_locations, strategy);
+                        notifications.notify_info(t!("location-definer-database-merged"));
+                    });
+                    locations_db.set(locations);
+                    merge_candidate_state.set(None);
+                })
+            };
+            let on_merge_cancelled = {
+                let merge_candidate_state = merge_candidate_state.clone();
+                Callback::from(move |_| merge_candidate_state.set(None))
+            };
+            html!(
+                <MergePreview
+                    locations_db={(*props.app_state.locations_db).clone()}
+                    {their_locations}
+                    {on_merge_accepted}
+                    {on_merge_cancelled}
+                />
+            )
+        }
+    };
+
+    let duplicates_open_state = use_state_eq(|| false);
+
+    let on_duplicates_request_cb = {
+        let duplicates_open_state = duplicates_open_state.clone();
+        Callback::from(move |_: MouseEvent| duplicates_open_state.set(true))
+    };
+
+    let duplicates_view = match *duplicates_open_state {
+        false => html!(),
+        true => {
+            let on_merge_accepted = {
+                let locations_db = props.app_state.locations_db.clone();
+                let notifications = props.app_state.notifications.clone();
+                let locations_list = locations_list.clone();
+                let selected_location_state = selected_location_state.clone();
+                let selected_location_index_state = selected_location_index_state.clone();
+                Callback::from(
+                    move |(kept, removed, selection): (Location, Location, FieldSelection)| {
+                        let mut locations = (*locations_db).clone();
+                        let notifications = notifications.clone();
+                        locations.use_locations_mut(move |locations| {
+                            match locations.merge_duplicates(&kept, &removed, &selection) {
+                                Ok(_) => notifications
+                                    .notify_info(t!("location-definer-duplicates-merged")),
+                                Err(err) => notifications.notify_error(err.to_string()),
+                            }
+                        });
+                        reselect_location(
+                            &locations,
+                            &locations_list,
+                            &selected_location_index_state,
+                            &selected_location_state,
+                        );
+                        locations_db.set(locations);
+                    },
+                )
+            };
+            let on_closed = {
+                let duplicates_open_state = duplicates_open_state.clone();
+                Callback::from(move |_| duplicates_open_state.set(false))
+            };
+            html!(
+                <DuplicatesView
+                    locations_db={(*props.app_state.locations_db).clone()}
+                    {on_merge_accepted}
+                    {on_closed}
+                />
+            )
+        }
+    };
+
+    let trash_open_state = use_state_eq(|| false);
+
+    let on_trash_request_cb = {
+        let trash_open_state = trash_open_state.clone();
+        Callback::from(move |_: MouseEvent| trash_open_state.set(true))
+    };
+
+    let trash_view = match *trash_open_state {
+        false => html!(),
+        true => {
+            let on_restore = {
+                let locations_db = props.app_state.locations_db.clone();
+                let notifications = props.app_state.notifications.clone();
+                Callback::from(move |location: Location| {
+                    let mut locations = (*locations_db).clone();
+                    let notifications = notifications.clone();
+                    locations.use_locations_mut(move |locations| {
+                        match locations.restore_from_trash(&location) {
+                            Ok(restored) => notifications.notify_info(t!(
+                                "location-definer-trash-restored",
+                                name = restored.name
+                            )),
+                            Err(err) => notifications.notify_error(err.to_string()),
+                        }
+                    });
+                    locations_db.set(locations);
+                })
+            };
+            let on_purge = {
+                let locations_db = props.app_state.locations_db.clone();
+                let notifications = props.app_state.notifications.clone();
+                Callback::from(move |location: Location| {
+                    let mut locations = (*locations_db).clone();
+                    let notifications = notifications.clone();
+                    locations.use_locations_mut(move |locations| match locations.purge(location) {
+                        Ok(purged) => notifications
+                            .notify_info(t!("location-definer-trash-purged", name = purged.name)),
+                        Err(err) => notifications.notify_error(err.to_string()),
+                    });
+                    locations_db.set(locations);
+                })
+            };
+            let on_closed = {
+                let trash_open_state = trash_open_state.clone();
+                Callback::from(move |_| trash_open_state.set(false))
+            };
+            html!(
+                <TrashView
+                    locations_db={(*props.app_state.locations_db).clone()}
+                    {on_restore}
+                    {on_purge}
+                    {on_closed}
+                />
+            )
+        }
+    };
+
+    let organisations_open_state = use_state_eq(|| false);
+
+    let on_organisations_request_cb = {
+        let organisations_open_state = organisations_open_state.clone();
+        Callback::from(move |_: MouseEvent| organisations_open_state.set(true))
+    };
+
+    let organisations_view = match *organisations_open_state {
+        false => html!(),
+        true => {
+            let on_save = {
+                let locations_db = props.app_state.locations_db.clone();
+                let notifications = props.app_state.notifications.clone();
+                Callback::from(move |organisation: Organisation| {
+                    let mut locations = (*locations_db).clone();
+                    let name = organisation.name.to_string();
+                    locations.use_locations_mut(move |locations| {
+                        locations.push_organisation(organisation)
+                    });
+                    notifications
+                        .notify_info(t!("location-definer-organisations-saved", name = name));
+                    locations_db.set(locations);
+                })
+            };
+            let on_remove = {
+                let locations_db = props.app_state.locations_db.clone();
+                let notifications = props.app_state.notifications.clone();
+                Callback::from(move |organisation: Organisation| {
+                    let mut locations = (*locations_db).clone();
+                    let notifications = notifications.clone();
+                    locations.use_locations_mut(move |locations| {
+                        match locations.remove_organisation(organisation.get_id()) {
+                            Ok(removed) => notifications.notify_info(t!(
+                                "location-definer-organisations-removed",
+                                name = removed.name
+                            )),
+                            Err(err) => notifications.notify_error(err.to_string()),
+                        }
+                    });
+                    locations_db.set(locations);
+                })
+            };
+            let on_closed = {
+                let organisations_open_state = organisations_open_state.clone();
+                Callback::from(move |_| organisations_open_state.set(false))
+            };
+            html!(
+                <OrganisationsView
+                    locations_db={(*props.app_state.locations_db).clone()}
+                    {on_save}
+                    {on_remove}
+                    {on_closed}
+                />
+            )
+        }
+    };
+
+    let mut organisations = vec![];
+    props.app_state.locations_db.use_locations(|locations| {
+        organisations = locations.organisations_in_order();
+    });
+
+    let bulk_selection_state: UseStateHandle<HashSet<Uuid>> = use_state_eq(HashSet::new);
+    let publication_filter_state: UseStateHandle<Option<PublicationState>> = use_state_eq(|| None);
+
+    let on_publication_filter_changed = {
+        let publication_filter_state = publication_filter_state.clone();
+        Callback::from(move |event: Event| {
+            if let Some(select) = event.target_dyn_into::<HtmlSelectElement>() {
+                let publication_filter = PublicationState::ALL
+                    .into_iter()
+                    .find(|state| format!("{state:?}") == select.value());
+                publication_filter_state.set(publication_filter);
+            }
+        })
+    };
+    let publication_filter_options: Html = PublicationState::ALL
+        .into_iter()
+        .map(|state| {
+            html!(
+                <option value={format!("{state:?}")} selected={*publication_filter_state == Some(state)}>
+                    {state.to_string()}
+                </option>
+            )
+        })
+        .collect();
+
+    let on_select_matching = {
+        let locations_db = props.app_state.locations_db.clone();
+        let bulk_selection_state = bulk_selection_state.clone();
+        Callback::from(move |query: Query| {
+            let mut selection = (*bulk_selection_state).clone();
+            locations_db.use_locations(|locations| {
+                let matching =
+                    locations.select(&BulkTarget::Matching(query), &current_query_context());
+                selection.extend(matching.iter().map(Location::get_id));
+            });
+            bulk_selection_state.set(selection);
+        })
+    };
+
+    let on_bulk_selection_cleared = {
+        let bulk_selection_state = bulk_selection_state.clone();
+        Callback::from(move |_| bulk_selection_state.set(HashSet::new()))
+    };
+
+    let on_bulk_action = {
+        let locations_db = props.app_state.locations_db.clone();
+        let notifications = props.app_state.notifications.clone();
+        let bulk_selection_state = bulk_selection_state.clone();
+        let locations_list = locations_list.clone();
+        let selected_location_state = selected_location_state.clone();
+        let selected_location_index_state = selected_location_index_state.clone();
+        Callback::from(move |action: BulkAction| {
+            let mut locations = (*locations_db).clone();
+            let notifications = notifications.clone();
+            let target = BulkTarget::Ids((*bulk_selection_state).clone());
+            let is_delete = action == BulkAction::Delete;
+            locations.use_locations_mut(move |locations| {
+                match locations.apply_bulk(&target, &current_query_context(), &action) {
+                    Ok(affected) => notifications
+                        .notify_info(t!("location-definer-bulk-applied", count = affected)),
+                    Err(err) => notifications.notify_error(err.to_string()),
+                }
+            });
+            if is_delete {
+                bulk_selection_state.set(HashSet::new());
+            }
+            reselect_location(
+                &locations,
+                &locations_list,
+                &selected_location_index_state,
+                &selected_location_state,
+            );
+            locations_db.set(locations);
+        })
+    };
+
+    let on_editor_name_changed = {
+        let notifications = props.app_state.notifications.clone();
+        Callback::from(move |event: Event| {
+            if let Some(input) = event.target_dyn_into::<HtmlInputElement>() {
+                if let Err(err) = set_editor_name(&input.value()) {
+                    notifications.notify_error(err);
+                }
+            }
+        })
+    };
+
+    let (can_undo, can_redo) = {
+        let mut can_undo_redo = (false, false);
+        props.app_state.locations_db.use_journal(|journal| {
+            can_undo_redo = (journal.can_undo(), journal.can_redo());
+        });
+        can_undo_redo
+    };
+    let on_undo_click = on_undo_request.reform(|_: MouseEvent| ());
+    let on_redo_click = on_redo_request.reform(|_: MouseEvent| ());
+
     let selected_location: Location = selected_location_state.deref().clone();
     info!("Editing location : {selected_location:?}");
 
@@ -196,6 +672,9 @@ pub fn location_definer(props: &LocationDefinerProps) -> Html {
         locations_list
             .iter()
             .enumerate()
+            .filter(|(_, loc)| {
+                publication_filter_state.is_none_or(|state| loc.publication_state() == state)
+            })
             .map(|(i, loc)| {
                 let selected_location_state = selected_location_state.clone();
                 let selected_location_index_state = selected_location_index_state.clone();
@@ -207,7 +686,29 @@ pub fn location_definer(props: &LocationDefinerProps) -> Html {
                     selection_cb: on_selected_cb,
                     state: (*loc) == selected_location,
                 };
-                html!(<LocationView location={loc.clone()} {selection_settings}/>)
+                let location_id = loc.get_id();
+                let on_bulk_selection_toggled = {
+                    let bulk_selection_state = bulk_selection_state.clone();
+                    Callback::from(move |event: Event| {
+                        if let Some(input) = event.target_dyn_into::<HtmlInputElement>() {
+                            let mut selection = (*bulk_selection_state).clone();
+                            match input.checked() {
+                                true => selection.insert(location_id),
+                                false => selection.remove(&location_id),
+                            };
+                            bulk_selection_state.set(selection);
+                        }
+                    })
+                };
+                html!(
+                    <div class="location-definer-list-item">
+                        <input type="checkbox" class="mr-2"
+                            checked={bulk_selection_state.contains(&location_id)}
+                            onchange={on_bulk_selection_toggled}/>
+                        <LocationView location={loc.clone()} {selection_settings}/>
+                        <span class="tag ml-2">{loc.publication_state().to_string()}</span>
+                    </div>
+                )
             })
             .collect()
     };
@@ -218,15 +719,29 @@ pub fn location_definer(props: &LocationDefinerProps) -> Html {
                 // The idea is that height is capped by LocationEdit window size
                 <div class="column is-one-third location-definer-column">
                     <div class="box location-definer-column-content">
+                        <div class="select is-small is-fullwidth mb-2">
+                            <select onchange={on_publication_filter_changed}>
+                                <option value="" selected={publication_filter_state.is_none()}>
+                                    {t!("location-definer-all-publication-states")}
+                                </option>
+                                {publication_filter_options}
+                            </select>
+                        </div>
                         { all_locations_view }
                     </div>
                 </div>
                 <div class="column is-two-thirds location-definer-column">
                     <div class="box location-definer-column-content">
-                        <LocationEdit initial_location_to_edit={selected_location} {location_edit_manager}/>
+                        <LocationEdit initial_location_to_edit={selected_location} {location_edit_manager} {organisations}/>
                     </div>
                 </div>
             </div>
+            <BulkActionsToolbar
+                selected_count={bulk_selection_state.len()}
+                {on_select_matching}
+                on_selection_cleared={on_bulk_selection_cleared}
+                on_action={on_bulk_action}
+            />
             <div class="box location-definer-buttons">
                 <button class="button is-rounded is-info" onclick={on_new_location_request_cb}>
                     { location_definer_add_label }
@@ -234,10 +749,37 @@ pub fn location_definer(props: &LocationDefinerProps) -> Html {
                 <button class="button is-rounded is-primary ml-2" onclick={on_db_save_request_cb}>
                     { location_definer_save_label }
                 </button>
+                <button class="button is-rounded is-link ml-2" onclick={on_db_merge_request_cb}>
+                    { location_definer_merge_label }
+                </button>
                 <button class="button is-rounded is-warning ml-2" onclick={on_db_load_request_cb}>
                     { location_definer_load_label }
                 </button>
+                <button class="button is-rounded ml-2" onclick={on_duplicates_request_cb}>
+                    { location_definer_duplicates_label }
+                </button>
+                <button class="button is-rounded ml-2" onclick={on_trash_request_cb}>
+                    { t!("location-definer-trash-label") }
+                </button>
+                <button class="button is-rounded ml-2" onclick={on_organisations_request_cb}>
+                    { t!("location-definer-organisations-label") }
+                </button>
+                <button class="button is-rounded ml-2" disabled={!can_undo} onclick={on_undo_click}>
+                    { location_definer_undo_label }
+                </button>
+                <button class="button is-rounded ml-2" disabled={!can_redo} onclick={on_redo_click}>
+                    { location_definer_redo_label }
+                </button>
+                <input class="input is-rounded ml-2 location-definer-editor-name" type="text"
+                    value={audit::editor_name()}
+                    placeholder={t!("location-definer-editor-name-placeholder")}
+                    title={t!("location-definer-editor-name-placeholder")}
+                    onchange={on_editor_name_changed}/>
             </div>
+            { merge_preview }
+            { duplicates_view }
+            { trash_view }
+            { organisations_view }
         </div>
     )
 }
@@ -0,0 +1,250 @@
+/*
+Copyright (C) 2025 Mateusz Mazur (Mazurel) <mateusz.mazur@e.email>
+
+This program is free software; you can redistribute it and/or
+modify it under the terms of the GNU General Public License
+as published by the Free Software Foundation; either version 2
+of the License, or (at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program; if not, see
+<https://www.gnu.org/licenses/>.
+*/
+
+use crate::yew_components::{ContactMethodsEdit, TagSelectionType, TagView};
+use libsopa::contact::ContactMethods;
+use libsopa::organisations::Organisation;
+use libsopa::tags::{get_all_supported_tags, Tag};
+use web_sys::{HtmlInputElement, HtmlTextAreaElement};
+use yew::prelude::*;
+
+use crate::locations::LocationsDatabase;
+
+#[derive(Properties, PartialEq)]
+pub struct OrganisationsViewProps {
+    pub locations_db: LocationsDatabase,
+    pub on_save: Callback<Organisation>,
+    pub on_remove: Callback<Organisation>,
+    pub on_closed: Callback<()>,
+}
+
+/// Modal listing organisations, with a form editing the fields inherited by their branches.
+#[function_component(OrganisationsView)]
+pub fn organisations_view(props: &OrganisationsViewProps) -> Html {
+    let edited_state: UseStateHandle<Option<Organisation>> = use_state_eq(|| None);
+
+    let mut organisations: Vec<(Organisation, usize)> = vec![];
+    props.locations_db.use_locations(|locations| {
+        organisations = locations
+            .organisations_in_order()
+            .into_iter()
+            .map(|organisation| {
+                let branches_count = locations.branches_of(organisation.get_id()).len();
+                (organisation, branches_count)
+            })
+            .collect();
+    });
+
+    let organisations_list = organisations
+        .iter()
+        .map(|(organisation, branches_count)| {
+            let is_edited = edited_state
+                .as_ref()
+                .is_some_and(|edited| edited.get_id() == organisation.get_id());
+            let onclick = {
+                let edited_state = edited_state.clone();
+                let organisation = organisation.clone();
+                Callback::from(move |_: MouseEvent| edited_state.set(Some(organisation.clone())))
+            };
+            html!(
+                <a class={classes!("panel-block", "is-clickable", is_edited.then_some("is-active"))} {onclick}>
+                    <span class="tag is-info is-light mr-2">{branches_count}</span>
+                    {organisation.name.to_string()}
+                </a>
+            )
+        })
+        .collect::<Html>();
+
+    let on_new_organisation = {
+        let on_save = props.on_save.clone();
+        let edited_state = edited_state.clone();
+        Callback::from(move |_: MouseEvent| {
+            let name =
+                gloo::dialogs::prompt(&t!("location-definer-organisations-new-prompt"), None);
+            if let Some(name) = name.filter(|name| !name.trim().is_empty()) {
+                let mut organisation = Organisation::default();
+                organisation
+                    .name
+                    .set(&rust_i18n::locale(), name.trim().to_string());
+                on_save.emit(organisation.clone());
+                edited_state.set(Some(organisation));
+            }
+        })
+    };
+
+    let edit_form = match (*edited_state).clone() {
+        None => html!(<p>{t!("location-definer-organisations-choose")}</p>),
+        Some(organisation) => {
+            // Organisations are edited in the language of the interface
+            let locale = rust_i18n::locale().to_string();
+            let on_name_changed = {
+                let edited_state = edited_state.clone();
+                let organisation = organisation.clone();
+                let locale = locale.clone();
+                Callback::from(move |event: Event| {
+                    if let Some(input) = event.target_dyn_into::<HtmlInputElement>() {
+                        let mut organisation = organisation.clone();
+                        organisation.name.set(&locale, input.value());
+                        edited_state.set(Some(organisation));
+                    }
+                })
+            };
+            let on_description_changed = {
+                let edited_state = edited_state.clone();
+                let organisation = organisation.clone();
+                let locale = locale.clone();
+                Callback::from(move |event: InputEvent| {
+                    if let Some(text_area) = event.target_dyn_into::<HtmlTextAreaElement>() {
+                        let mut organisation = organisation.clone();
+                        organisation.description.set(&locale, text_area.value());
+                        edited_state.set(Some(organisation));
+                    }
+                })
+            };
+            let on_methods_changed = {
+                let edited_state = edited_state.clone();
+                let organisation = organisation.clone();
+                Callback::from(move |contact_methods: ContactMethods| {
+                    let mut organisation = organisation.clone();
+                    organisation.contact_methods = contact_methods;
+                    edited_state.set(Some(organisation));
+                })
+            };
+            let selection_changed = {
+                let edited_state = edited_state.clone();
+                let organisation = organisation.clone();
+                Callback::from(move |(tag, new_state): (Tag, TagSelectionType)| {
+                    let mut organisation = organisation.clone();
+                    organisation.tags = match new_state {
+                        TagSelectionType::Acceptable => organisation.tags.with_tag(tag),
+                        // Definer only uses two-state tags
+                        _ => organisation.tags.without_tag(tag),
+                    };
+                    edited_state.set(Some(organisation));
+                })
+            };
+            let tags = get_all_supported_tags()
+                .get_all_tags_in_order()
+                .into_iter()
+                .map(|tag| {
+                    let selection_type = match organisation.tags.has_tag(tag) {
+                        true => TagSelectionType::Acceptable,
+                        false => TagSelectionType::NonAcceptable,
+                    };
+                    html!(<TagView
+                        tag={tag.clone()}
+                        interactive={true}
+                        selection_changed={Some(selection_changed.clone())}
+                        {selection_type}
+                    />)
+                })
+                .collect::<Html>();
+            let on_save = {
+                let organisation = organisation.clone();
+                props
+                    .on_save
+                    .reform(move |_: MouseEvent| organisation.clone())
+            };
+            let on_remove = {
+                let on_remove = props.on_remove.clone();
+                let edited_state = edited_state.clone();
+                let organisation = organisation.clone();
+                Callback::from(move |_: MouseEvent| {
+                    let confirm_remove = t!(
+                        "location-definer-organisations-confirm-remove",
+                        name = organisation.name
+                    );
+                    if gloo::dialogs::confirm(&confirm_remove) {
+                        on_remove.emit(organisation.clone());
+                        edited_state.set(None);
+                    }
+                })
+            };
+
+            html!(
+                <>
+                    <p class="help mb-3">{t!("location-definer-organisations-inheritance-help")}</p>
+                    <div class="field">
+                        <div class="label">{t!("location-definer-title-label")}</div>
+                        <div class="control">
+                            <input class="input" type="text"
+                                value={organisation.name.translation(&locale).unwrap_or_default().to_string()}
+                                placeholder={organisation.name.get(&locale).to_string()}
+                                onchange={on_name_changed}/>
+                        </div>
+                    </div>
+                    <div class="field">
+                        <div class="label">{t!("location-definer-description-label")}</div>
+                        <div class="control">
+                            <textarea class="textarea"
+                                value={organisation.description.translation(&locale).unwrap_or_default().to_string()}
+                                placeholder={organisation.description.get(&locale).to_string()}
+                                oninput={on_description_changed}/>
+                        </div>
+                    </div>
+                    <ContactMethodsEdit methods={organisation.contact_methods.clone()} {on_methods_changed}/>
+                    <div class="field">
+                        <div class="label">{t!("location-definer-tags-label")}</div>
+                        <div class="control">{tags}</div>
+                    </div>
+                    <div class="buttons">
+                        <button class="button is-primary" onclick={on_save}>
+                            {t!("location-definer-organisations-save")}
+                        </button>
+                        <button class="button is-danger is-light" onclick={on_remove}>
+                            {t!("location-definer-organisations-remove")}
+                        </button>
+                    </div>
+                </>
+            )
+        }
+    };
+
+    let on_close = props.on_closed.reform(|_: MouseEvent| ());
+
+    html!(
+        <div class="modal is-active">
+            <div class="modal-background"></div>
+            <div class="modal-card" style="width: 80%;">
+                <header class="modal-card-head">
+                    <p class="modal-card-title">{t!("location-definer-organisations-title")}</p>
+                </header>
+                <section class="modal-card-body">
+                    <div class="columns">
+                        <div class="column is-one-third">
+                            <div class="panel">
+                                {organisations_list}
+                                <div class="panel-block">
+                                    <button class="button is-info is-fullwidth" onclick={on_new_organisation}>
+                                        {t!("location-definer-organisations-new")}
+                                    </button>
+                                </div>
+                            </div>
+                        </div>
+                        <div class="column">{edit_form}</div>
+                    </div>
+                </section>
+                <footer class="modal-card-foot buttons">
+                    <button class="button" onclick={on_close}>
+                        {t!("location-definer-organisations-close")}
+                    </button>
+                </footer>
+            </div>
+        </div>
+    )
+}
@@ -49,7 +49,8 @@ pub fn tags_selection_edit_for_location(props: &TagsSelectionEditForLocationProp
                     location_edit_manager.stage_location_changes(location);
                 }
             }
-            TagSelectionType::NonAcceptable => {
+            // Definer only uses two-state tags
+            _ => {
                 let mut location = location_edit_manager.get_location_under_edit();
                 if location.tags.has_tag(&tag) {
                     location.tags = location.tags.without_tag(tag.clone());
@@ -61,14 +62,46 @@ pub fn tags_selection_edit_for_location(props: &TagsSelectionEditForLocationProp
     };
 
     let location_tags = location.tags.clone();
+    let suggested_tags = location.suggested_geo_tags();
 
-    get_all_supported_tags()
+    let accept_suggestions_on_click = {
+        let location_edit_manager = location_edit_manager.clone();
+        Callback::from(move |_: MouseEvent| {
+            let mut location = location_edit_manager.get_location_under_edit();
+            for tag in location.suggested_geo_tags() {
+                location.tags = location.tags.with_tag(tag);
+            }
+            location_edit_manager.stage_location_changes(location);
+        })
+    };
+
+    let suggestions = match suggested_tags.is_empty() {
+        true => html!(),
+        false => {
+            let suggested_tag_names = suggested_tags
+                .iter()
+                .map(|tag| tag.human_readable().to_string())
+                .collect::<Vec<_>>()
+                .join(", ");
+            html!(
+                <div class="notification is-warning is-light">
+                    {t!("location-definer-suggested-tags", tags = suggested_tag_names)}
+                    <button class="button is-small is-warning ml-3" onclick={accept_suggestions_on_click}>
+                        {t!("location-definer-accept-suggested-tags")}
+                    </button>
+                </div>
+            )
+        }
+    };
+
+    let tags = get_all_supported_tags()
         .get_all_tags_in_order()
         .into_iter()
         .map(|tag| {
-            let tag_state = match location_tags.has_tag(tag) {
-                false => TagSelectionType::NonAcceptable,
-                true => TagSelectionType::Acceptable,
+            let tag_state = match (location_tags.has_tag(tag), suggested_tags.contains(tag)) {
+                (true, _) => TagSelectionType::Acceptable,
+                (false, true) => TagSelectionType::Suggested,
+                (false, false) => TagSelectionType::NonAcceptable,
             };
             html!(<TagView
                 tag={tag.clone()}
@@ -77,5 +110,7 @@ pub fn tags_selection_edit_for_location(props: &TagsSelectionEditForLocationProp
                 selection_type={tag_state}
             />)
         })
-        .collect::<Html>()
+        .collect::<Html>();
+
+    html!(<>{suggestions}{tags}</>)
 }
@@ -0,0 +1,107 @@
+/*
+Copyright (C) 2025 Mateusz Mazur (Mazurel) <mateusz.mazur@e.email>
+
+This program is free software; you can redistribute it and/or
+modify it under the terms of the GNU General Public License
+as published by the Free Software Foundation; either version 2
+of the License, or (at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program; if not, see
+<https://www.gnu.org/licenses/>.
+*/
+
+use libsopa::locations::Location;
+use yew::prelude::*;
+
+use crate::locations::LocationsDatabase;
+
+#[derive(Properties, PartialEq)]
+pub struct TrashViewProps {
+    pub locations_db: LocationsDatabase,
+    pub on_restore: Callback<Location>,
+    pub on_purge: Callback<Location>,
+    pub on_closed: Callback<()>,
+}
+
+/// Lists deleted locations, which can be restored or removed permanently.
+#[function_component(TrashView)]
+pub fn trash_view(props: &TrashViewProps) -> Html {
+    let mut trash = vec![];
+    props.locations_db.use_locations(|locations| {
+        trash = locations.trash();
+    });
+
+    let trash_rows = trash
+        .into_iter()
+        .map(|location| {
+            let deleted_at = location
+                .deleted_at()
+                .map(|deleted_at| deleted_at.to_string())
+                .unwrap_or_default();
+            let on_restore = {
+                let location = location.clone();
+                props.on_restore.reform(move |_: MouseEvent| location.clone())
+            };
+            let on_purge = {
+                let on_purge = props.on_purge.clone();
+                let location = location.clone();
+                Callback::from(move |_: MouseEvent| {
+                    let confirm_purge =
+                        t!("location-definer-trash-confirm-purge", name = location.name);
+                    if gloo::dialogs::confirm(&confirm_purge) {
+                        on_purge.emit(location.clone());
+                    }
+                })
+            };
+            html!(
+                <tr>
+                    <td>{location.name.to_string()}</td>
+                    <td>{t!("location-definer-trash-deleted-on", date = deleted_at)}</td>
+                    <td>
+                        <div class="buttons is-right">
+                            <button class="button is-small is-success is-light" onclick={on_restore}>
+                                {t!("location-definer-trash-restore")}
+                            </button>
+                            <button class="button is-small is-danger is-light" onclick={on_purge}>
+                                {t!("location-definer-trash-purge")}
+                            </button>
+                        </div>
+                    </td>
+                </tr>
+            )
+        })
+        .collect::<Vec<Html>>();
+
+    let on_close = props.on_closed.reform(|_: MouseEvent| ());
+
+    html!(
+        <div class="modal is-active">
+            <div class="modal-background"></div>
+            <div class="modal-card" style="width: 80%;">
+                <header class="modal-card-head">
+                    <p class="modal-card-title">{t!("location-definer-trash-title")}</p>
+                </header>
+                <section class="modal-card-body">
+                    if trash_rows.is_empty() {
+                        <p>{t!("location-definer-trash-empty")}</p>
+                    } else {
+                        <table class="table is-fullwidth">
+                            <tbody>{trash_rows}</tbody>
+                        </table>
+                    }
+                </section>
+                <footer class="modal-card-foot buttons">
+                    <button class="button" onclick={on_close}>
+                        {t!("location-definer-trash-close")}
+                    </button>
+                </footer>
+            </div>
+        </div>
+    )
+}
@@ -18,17 +18,32 @@ along with this program; if not, see
 
 mod tag_selection;
 
+use crate::geolocation::request_current_position;
+use crate::yew_components::accessibility::get_accessibility_fontawesome_icon;
 use crate::yew_components::LocationView;
-use libsopa::locations::Location;
-use libsopa::tags::Tags;
+use libsopa::accessibility::AccessibilityFeature;
+use libsopa::audit;
+use libsopa::gazetteer::known_cities;
+use libsopa::geo::{sort_by_distance, GeoPoint};
+use libsopa::locations::Locations;
+use libsopa::query::{Query, QueryContext, QueryResult};
+use libsopa::ranking::{RankedLocation, RankingWeights};
+use libsopa::search::SearchIndex;
+use libsopa::tags::{Tag, TagPreference, Tags};
+use libsopa::time::{Day, TimePoint};
+use std::rc::Rc;
 use tag_selection::TagPreferenceSelection;
+use web_sys::{HtmlInputElement, HtmlSelectElement};
 use yew::prelude::*;
 
 use crate::app::SharedAppState;
+use crate::window_location::{read_window_query, set_window_query};
 
-#[derive(Properties, PartialEq, Eq)]
+#[derive(Properties, PartialEq)]
 struct LocationsViewProps {
-    locations: Vec<Location>,
+    locations: Vec<RankedLocation>,
+    /// Database the locations come from, used to find other branches of their organisations.
+    published_locations: Rc<Locations>,
     selected_tags: Tags,
 }
 
@@ -36,7 +51,11 @@ struct LocationsViewProps {
 fn locations_view(props: &LocationsViewProps) -> Html {
     let all_locations = props.locations
         .iter()
-        .map(|l| html!(<LocationView location={l.clone()} global_selected_tags={props.selected_tags.clone()} simplified_view={false}/>))
+        .map(|l| {
+            let organisation = props.published_locations.organisation_of(&l.location).cloned();
+            let other_branches = props.published_locations.other_branches(&l.location);
+            html!(<LocationView location={l.location.clone()} global_selected_tags={props.selected_tags.clone()} ranking_explanation={l.explanation.clone()} simplified_view={false} {organisation} {other_branches}/>)
+        })
         .collect::<Vec<_>>();
 
     html!(
@@ -51,39 +70,301 @@ pub struct LocationFinderProps {
     pub app_state: SharedAppState,
 }
 
-fn fetch_all_locations(db: &crate::locations::LocationsDatabase) -> Vec<Location> {
-    let mut locs = Vec::new();
+/// Locations shown in the finder, with the fields inherited from their organisations.
+fn fetch_published_locations(db: &crate::locations::LocationsDatabase) -> Locations {
+    let mut published_locations = Locations::new();
     db.use_locations(|locations| {
-        locs.append(&mut locations.locations_in_random_order());
+        published_locations = locations.published().with_inheritance();
     });
-    locs
+    published_locations
+}
+
+pub(crate) fn current_query_context() -> QueryContext {
+    let date = js_sys::Date::new_0();
+    let day = match date.get_day() {
+        0 => Day::Sunday,
+        1 => Day::Monday,
+        2 => Day::Tuesday,
+        3 => Day::Wednesday,
+        4 => Day::Thursday,
+        5 => Day::Friday,
+        _ => Day::Saturday,
+    };
+    let time = TimePoint {
+        hour: date.get_hours() as u8,
+        minute: date.get_minutes() as u8,
+    };
+    QueryContext {
+        now: Some((day, time)),
+        timestamp: Some(audit::now()),
+    }
 }
 
+/// Empty advanced query means no filtering.
+fn parse_advanced_query(advanced_query: &str) -> Option<QueryResult<Query>> {
+    match advanced_query.trim().is_empty() {
+        true => None,
+        false => Some(advanced_query.parse()),
+    }
+}
+
+const NEAREST_TO_MY_LOCATION: &str = "my-location";
+
 #[function_component(LocationFinder)]
 pub fn location_finder(props: &LocationFinderProps) -> Html {
-    let locations_in_order_state = {
-        let locations = fetch_all_locations(&props.app_state.locations_db);
-        use_state_eq(move || locations)
+    // Bumped whenever the database changes, so that the memoized data is rebuilt
+    let db_generation_state = use_state(|| 0u32);
+    {
+        let locations_db = props.app_state.locations_db.clone();
+        let db_generation_state = db_generation_state.clone();
+        use_effect_with((), move |_| {
+            let mut locations_db = (*locations_db).clone();
+            locations_db.register_db_changed_callback(Callback::from(move |_| {
+                db_generation_state.set(db_generation_state.wrapping_add(1));
+            }));
+        });
+    }
+    let db_key = (
+        (*props.app_state.locations_db).clone(),
+        *db_generation_state,
+    );
+    let published_locations = use_memo(db_key.clone(), |(locations_db, _)| {
+        fetch_published_locations(locations_db)
+    });
+    let search_index = {
+        let published_locations = published_locations.clone();
+        use_memo(db_key, move |_| SearchIndex::new(&published_locations))
     };
-    let tag_preference_state = use_state_eq(|| Tags::new());
+
+    let locations_in_order_state: UseStateHandle<Vec<RankedLocation>> = use_state_eq(Vec::new);
+    let tag_preference_state = use_state_eq(TagPreference::new);
+    let search_query_state = use_state_eq(String::new);
+    let search_suggestion_state: UseStateHandle<Option<String>> = use_state_eq(|| None);
+    let no_search_matches_state = use_state_eq(|| false);
+    let advanced_query_state =
+        use_state_eq(|| read_window_query().advanced_query.unwrap_or_default());
+    let parsed_advanced_query = parse_advanced_query(&advanced_query_state);
+    // Value of the "nearest first" select, empty when locations are not sorted by distance
+    let nearest_first_state = use_state_eq(String::new);
+    let distance_origin_state: UseStateHandle<Option<GeoPoint>> = use_state_eq(|| None);
+    let required_accessibility_state: UseStateHandle<Vec<AccessibilityFeature>> =
+        use_state_eq(Vec::new);
+    let free_only_state = use_state_eq(|| false);
+    let no_referral_only_state = use_state_eq(|| false);
 
     {
-        let locations_db = props.app_state.locations_db.clone();
         let locations_state = locations_in_order_state.clone();
-        let tag_preference_state = tag_preference_state.clone();
-        use_effect(move || {
-            locations_db.use_locations(move |locations| {
-                let new_locations: Vec<Location> = locations
-                    .all_locations_in_order(&tag_preference_state)
-                    .into();
+        let search_suggestion_state = search_suggestion_state.clone();
+        let no_search_matches_state = no_search_matches_state.clone();
+        let published_locations = published_locations.clone();
+        let search_index = search_index.clone();
+        // Ranking is redone only when the database, the query or the filters change
+        use_effect_with(
+            (
+                published_locations,
+                (*search_query_state).clone(),
+                (*tag_preference_state).clone(),
+                (*advanced_query_state).clone(),
+                *distance_origin_state,
+                (*required_accessibility_state).clone(),
+                *free_only_state,
+                *no_referral_only_state,
+            ),
+            move |(
+                locations,
+                search_query,
+                tag_preference,
+                advanced_query,
+                distance_origin,
+                required_accessibility,
+                free_only,
+                no_referral_only,
+            )| {
+                let ranking_weights = RankingWeights::new();
+                let mut new_locations: Vec<RankedLocation> = locations.search_in_order(
+                    &search_index,
+                    search_query,
+                    tag_preference,
+                    &ranking_weights,
+                );
+                // Rather than showing nothing, show everything matching the tags
+                let no_search_matches = new_locations.is_empty();
+                if no_search_matches {
+                    new_locations = locations.rank(tag_preference, &ranking_weights);
+                }
+                if let Some(Ok(advanced_query)) = parse_advanced_query(advanced_query) {
+                    let context = current_query_context();
+                    new_locations
+                        .retain(|ranked| advanced_query.matches(&ranked.location, &context));
+                }
+                new_locations.retain(|ranked| {
+                    let location = &ranked.location;
+                    location.offers_accessibility(required_accessibility)
+                        && (!free_only || location.is_free())
+                        && (!no_referral_only || location.needs_no_referral())
+                });
+                if let Some(distance_origin) = distance_origin {
+                    sort_by_distance(&mut new_locations, distance_origin);
+                }
+                search_suggestion_state.set(search_index.suggest(search_query));
+                no_search_matches_state.set(no_search_matches);
                 locations_state.set(new_locations);
-            });
-        });
+            },
+        );
     }
 
+    let on_search_query_changed = {
+        let search_query_state = search_query_state.clone();
+        Callback::from(move |event: InputEvent| {
+            if let Some(input) = event.target_dyn_into::<HtmlInputElement>() {
+                search_query_state.set(input.value());
+            }
+        })
+    };
+
+    let on_advanced_query_changed = {
+        let advanced_query_state = advanced_query_state.clone();
+        Callback::from(move |event: InputEvent| {
+            if let Some(input) = event.target_dyn_into::<HtmlInputElement>() {
+                let advanced_query = input.value();
+                // Only valid queries are put in the URL, so that it can be shared
+                match parse_advanced_query(&advanced_query) {
+                    None => set_window_query(&Default::default()),
+                    Some(Ok(query)) => set_window_query(&crate::window_location::Query {
+                        advanced_query: Some(query.to_string()),
+                    }),
+                    Some(Err(_)) => {}
+                }
+                advanced_query_state.set(advanced_query);
+            }
+        })
+    };
+
+    let on_nearest_first_changed = {
+        let nearest_first_state = nearest_first_state.clone();
+        let distance_origin_state = distance_origin_state.clone();
+        let notifications = props.app_state.notifications.clone();
+        Callback::from(move |event: Event| {
+            if let Some(select) = event.target_dyn_into::<HtmlSelectElement>() {
+                let nearest_first = select.value();
+                nearest_first_state.set(nearest_first.clone());
+                match nearest_first.as_str() {
+                    NEAREST_TO_MY_LOCATION => {
+                        let nearest_first_state = nearest_first_state.clone();
+                        let distance_origin_state = distance_origin_state.clone();
+                        let notifications = notifications.clone();
+                        request_current_position(Callback::from(
+                            move |position: Option<GeoPoint>| {
+                                if position.is_none() {
+                                    notifications
+                                        .notify_warning(t!("location-finder-position-unavailable"));
+                                    nearest_first_state.set(String::new());
+                                }
+                                distance_origin_state.set(position);
+                            },
+                        ));
+                    }
+                    city_tag_name => distance_origin_state.set(
+                        known_cities()
+                            .find(|city| city.tag_name == city_tag_name)
+                            .map(|city| city.center),
+                    ),
+                }
+            }
+        })
+    };
+
+    let nearest_first_options = known_cities()
+        .map(|city| {
+            let city_tag = Tag::new(city.tag_name.to_string());
+            html!(
+                <option value={city.tag_name} selected={*nearest_first_state == city.tag_name}>
+                    {t!("location-finder-nearest-to", place = city_tag.human_readable())}
+                </option>
+            )
+        })
+        .collect::<Html>();
+
+    let accessibility_buttons = AccessibilityFeature::ALL
+        .into_iter()
+        .map(|feature| {
+            let is_required = required_accessibility_state.contains(&feature);
+            let onclick = {
+                let required_accessibility_state = required_accessibility_state.clone();
+                Callback::from(move |_: MouseEvent| {
+                    let mut required_accessibility = (*required_accessibility_state).clone();
+                    match is_required {
+                        true => required_accessibility.retain(|required| *required != feature),
+                        false => required_accessibility.push(feature),
+                    }
+                    required_accessibility_state.set(required_accessibility);
+                })
+            };
+            html!(
+                <button
+                    class={classes!("button", "is-rounded", is_required.then_some("is-info"))}
+                    title={feature.to_string()}
+                    {onclick}>
+                    <span class="icon">
+                        <i class={classes!("fas", get_accessibility_fontawesome_icon(feature))}></i>
+                    </span>
+                    <span>{feature.to_string()}</span>
+                </button>
+            )
+        })
+        .collect::<Html>();
+
+    let service_terms_button = |state: &UseStateHandle<bool>, label: String| {
+        let is_on = **state;
+        let onclick = {
+            let state = state.clone();
+            Callback::from(move |_: MouseEvent| state.set(!is_on))
+        };
+        html!(
+            <button class={classes!("button", "is-rounded", is_on.then_some("is-success"))} {onclick}>
+                {label}
+            </button>
+        )
+    };
+
+    let (advanced_query_class, advanced_query_help) = match &parsed_advanced_query {
+        Some(Err(err)) => (
+            "input is-rounded is-danger",
+            html!(<p class="help is-danger is-size-6">{err.to_string()}</p>),
+        ),
+        _ => (
+            "input is-rounded",
+            html!(<p class="help is-size-6">{t!("location-finder-advanced-query-help")}</p>),
+        ),
+    };
+
+    let search_hints = {
+        let suggestion = match (*search_suggestion_state).clone() {
+            Some(suggestion) => {
+                let search_query_state = search_query_state.clone();
+                let onclick = {
+                    let suggestion = suggestion.clone();
+                    Callback::from(move |_: MouseEvent| search_query_state.set(suggestion.clone()))
+                };
+                html!(
+                    <p class="help is-size-6">
+                        {t!("location-finder-did-you-mean")}{" "}
+                        <a {onclick}>{suggestion}</a>
+                    </p>
+                )
+            }
+            None => html!(),
+        };
+        let no_matches = match *no_search_matches_state {
+            true => html!(<p class="help is-size-6">{t!("location-finder-no-matches")}</p>),
+            false => html!(),
+        };
+        html!(<>{no_matches}{suggestion}</>)
+    };
+
     let on_tag_preference_changed = {
         let tag_preference_state = tag_preference_state.clone();
-        Callback::from(move |tag_preference: Tags| {
+        Callback::from(move |tag_preference: TagPreference| {
             tag_preference_state.set(tag_preference);
         })
     };
@@ -91,8 +372,52 @@ pub fn location_finder(props: &LocationFinderProps) -> Html {
     html! {
         <div id="location-finder-root" class="block">
             <TagPreferenceSelection {on_tag_preference_changed}/>
+            <div class="container mt-2">
+                <input
+                    class="input is-rounded"
+                    type="search"
+                    placeholder={t!("location-finder-search-placeholder")}
+                    value={(*search_query_state).clone()}
+                    oninput={on_search_query_changed}/>
+                {search_hints}
+            </div>
+            <div class="container mt-2">
+                <label class="label">{t!("location-finder-nearest-first-label")}</label>
+                <div class="select is-rounded">
+                    <select onchange={on_nearest_first_changed}>
+                        <option value="" selected={nearest_first_state.is_empty()}>
+                            {t!("location-finder-nearest-first-off")}
+                        </option>
+                        <option value={NEAREST_TO_MY_LOCATION} selected={*nearest_first_state == NEAREST_TO_MY_LOCATION}>
+                            {t!("location-finder-nearest-to-my-location")}
+                        </option>
+                        {nearest_first_options}
+                    </select>
+                </div>
+            </div>
+            <div class="container mt-2">
+                <label class="label">{t!("location-finder-accessibility-label")}</label>
+                <div class="buttons">{accessibility_buttons}</div>
+            </div>
+            <div class="container mt-2">
+                <label class="label">{t!("location-finder-service-terms-label")}</label>
+                <div class="buttons">
+                    {service_terms_button(&free_only_state, t!("location-finder-free-only").to_string())}
+                    {service_terms_button(&no_referral_only_state, t!("location-finder-no-referral-only").to_string())}
+                </div>
+            </div>
+            <div class="container mt-2">
+                <label class="label">{t!("location-finder-advanced-query-label")}</label>
+                <input
+                    class={advanced_query_class}
+                    type="text"
+                    placeholder="tag:LGBT AND tag:Gdańsk AND open:now AND NOT tag:NFZ"
+                    value={(*advanced_query_state).clone()}
+                    oninput={on_advanced_query_changed}/>
+                {advanced_query_help}
+            </div>
             <div class="container">
-                <LocationsView locations={(*locations_in_order_state).clone()} selected_tags={(*tag_preference_state).clone()}/>
+                <LocationsView locations={(*locations_in_order_state).clone()} published_locations={published_locations.clone()} selected_tags={tag_preference_state.wanted()}/>
             </div>
         </div>
     }
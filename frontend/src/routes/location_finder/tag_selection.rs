@@ -1,34 +1,42 @@
 use crate::yew_components::{TagSelectionType, TagView};
 use libsopa::tags::{
-    get_all_supported_tags_of_group, get_all_supported_tags_without_group, Tag, TagGroup, Tags,
+    get_all_supported_tags_of_group, get_all_supported_tags_without_group, MatchingMode, Tag,
+    TagGroup, TagPreference, TagPreferenceKind,
 };
 use yew::prelude::*;
 
-pub type TagPreference = Tags;
+fn preference_into_selection_type(kind: Option<TagPreferenceKind>) -> TagSelectionType {
+    match kind {
+        None => TagSelectionType::Unselected,
+        Some(TagPreferenceKind::Preferred) => TagSelectionType::Acceptable,
+        Some(TagPreferenceKind::Required) => TagSelectionType::Required,
+        Some(TagPreferenceKind::Excluded) => TagSelectionType::Excluded,
+    }
+}
 
-fn togglable_tag(selected_tags_state: UseStateHandle<Tags>, tag: Tag) -> Html {
-    let current_tag_selection_type = match selected_tags_state.has_tag(&tag) {
-        false => TagSelectionType::NonAcceptable,
-        true => TagSelectionType::Acceptable,
-    };
+fn selection_type_into_preference(selection_type: TagSelectionType) -> Option<TagPreferenceKind> {
+    match selection_type {
+        TagSelectionType::Acceptable => Some(TagPreferenceKind::Preferred),
+        TagSelectionType::Required => Some(TagPreferenceKind::Required),
+        TagSelectionType::Excluded => Some(TagPreferenceKind::Excluded),
+        TagSelectionType::NonAcceptable
+        | TagSelectionType::Unselected
+        | TagSelectionType::Suggested => None,
+    }
+}
+
+fn togglable_tag(selected_tags_state: UseStateHandle<TagPreference>, tag: Tag) -> Html {
+    let current_tag_selection_type = preference_into_selection_type(selected_tags_state.get(&tag));
     let cb = move |(tag, state): (Tag, TagSelectionType)| {
-        use TagSelectionType::*;
-        match state {
-            Acceptable => {
-                let new_tags = selected_tags_state.with_tag(tag);
-                selected_tags_state.set(new_tags.clone());
-            }
-            NonAcceptable => {
-                let new_tags = selected_tags_state.without_tag(tag);
-                selected_tags_state.set(new_tags.clone());
-            }
-        }
+        let new_preference = selected_tags_state.with(&tag, selection_type_into_preference(state));
+        selected_tags_state.set(new_preference);
     };
     html!(
         <TagView
             tag={tag.clone()}
             selection_type={current_tag_selection_type}
             selection_changed={cb}
+            tri_state={true}
         />
     )
 }
@@ -40,7 +48,9 @@ pub struct TagSelectionProps {
 
 #[function_component(TagPreferenceSelection)]
 pub fn tag_selection(props: &TagSelectionProps) -> Html {
-    let selected_tags_state = use_state_eq(|| Tags::new());
+    // Within a group any of the selected tags is enough, e.g. any of the cities
+    let selected_tags_state =
+        use_state_eq(|| TagPreference::new().with_matching_mode(MatchingMode::GroupAware));
     let tag_selection = t!("select-tags");
 
     {
@@ -77,6 +87,7 @@ pub fn tag_selection(props: &TagSelectionProps) -> Html {
         grouped_tags_elements.push(html!(
             <div class="block">
                 <h4 style="margin-bottom: 0.1em;" class="subtitle is-4">{tag_group_name}</h4>
+                <p class="help">{t!("select-tags-group-any")}</p>
                 <div class="is-flex is-flex-direction-row is-flex-wrap-wrap tag-flex">
                     {grouped_interactive_tags}
                 </div>
@@ -89,6 +100,7 @@ pub fn tag_selection(props: &TagSelectionProps) -> Html {
             <section class="hero">
               <div class="hero-body ml-auto mr-auto">
                 <p class="title">{tag_selection}</p>
+                <p class="subtitle is-6">{t!("select-tags-help")}</p>
               </div>
             </section>
             <div class="columns ml-6 mr-6">
@@ -1,13 +1,16 @@
 use gloo::history::{BrowserHistory, History};
-use serde::Serialize;
+use log::warn;
+use serde::{Deserialize, Serialize};
 
 pub type Path = String;
 
-// TODO: Allow to add query parameters and handle them somewhere.
-//       use: `gloo::history::query` - https://docs.rs/gloo/latest/gloo/history/query/index.html
-#[derive(Serialize)]
-#[allow(unused)]
-pub struct Query {}
+/// Query parameters of the page URL, so that views can be shared as links.
+#[derive(Serialize, Deserialize, Default)]
+pub struct Query {
+    /// Filter query of the location finder, see `libsopa::query`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub advanced_query: Option<String>,
+}
 
 fn get_history() -> BrowserHistory {
     gloo::history::BrowserHistory::new()
@@ -20,3 +23,16 @@ pub fn read_window_path() -> Path {
 pub fn set_window_path(path: String) {
     get_history().push(path);
 }
+
+pub fn read_window_query() -> Query {
+    get_history()
+        .location()
+        .query::<Query>()
+        .unwrap_or_default()
+}
+
+pub fn set_window_query(query: &Query) {
+    if let Err(err) = get_history().replace_with_query(read_window_path(), query) {
+        warn!("Failed setting window query: {err:?}");
+    }
+}
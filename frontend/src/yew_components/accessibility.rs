@@ -0,0 +1,122 @@
+/*
+Copyright (C) 2025 Mateusz Mazur (Mazurel) <mateusz.mazur@e.email>
+
+This program is free software; you can redistribute it and/or
+modify it under the terms of the GNU General Public License
+as published by the Free Software Foundation; either version 2
+of the License, or (at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program; if not, see
+<https://www.gnu.org/licenses/>.
+*/
+
+use libsopa::accessibility::{Accessibility, AccessibilityFeature, Availability};
+use yew::prelude::*;
+
+pub fn get_accessibility_fontawesome_icon(feature: AccessibilityFeature) -> &'static str {
+    match feature {
+        AccessibilityFeature::StepFreeAccess => "fa-wheelchair",
+        AccessibilityFeature::AccessibleToilet => "fa-restroom",
+        AccessibilityFeature::InductionLoop => "fa-assistive-listening-systems",
+        AccessibilityFeature::SignLanguageInterpreter => "fa-sign-language",
+        AccessibilityFeature::EasyRead => "fa-book-open",
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct AccessibilityViewProps {
+    pub accessibility: Accessibility,
+}
+
+/// Icons of the features with known availability, unavailable ones are greyed out.
+#[function_component(AccessibilityView)]
+pub fn accessibility_view(props: &AccessibilityViewProps) -> Html {
+    let known_features = props.accessibility.known_features();
+    let icons = known_features
+        .iter()
+        .map(|(feature, availability)| {
+            let (title, class) = match availability {
+                Availability::No => (
+                    t!("accessibility-not-available", feature = feature).to_string(),
+                    "has-text-grey-light",
+                ),
+                _ => (feature.to_string(), "has-text-info"),
+            };
+            html!(
+                <span class={classes!("icon", "is-medium", class)} {title}>
+                    <i class={classes!("fas", "fa-lg", get_accessibility_fontawesome_icon(*feature))}></i>
+                </span>
+            )
+        })
+        .collect::<Html>();
+
+    html!(
+        if !known_features.is_empty() {
+            <div class="ml-6 mt-2">{icons}</div>
+        }
+    )
+}
+
+#[derive(Properties, PartialEq)]
+pub struct AccessibilityEditProps {
+    pub accessibility: Accessibility,
+    pub on_accessibility_changed: Callback<Accessibility>,
+}
+
+#[function_component(AccessibilityEdit)]
+pub fn accessibility_edit(props: &AccessibilityEditProps) -> Html {
+    let rows = AccessibilityFeature::ALL
+        .into_iter()
+        .map(|feature| {
+            let current = props.accessibility.get(feature);
+            let buttons = Availability::ALL
+                .into_iter()
+                .map(|availability| {
+                    let onclick = {
+                        let on_accessibility_changed = props.on_accessibility_changed.clone();
+                        let mut accessibility = props.accessibility.clone();
+                        accessibility.set(feature, availability);
+                        Callback::from(move |_: MouseEvent| {
+                            on_accessibility_changed.emit(accessibility.clone())
+                        })
+                    };
+                    let selected_class = match (current == availability, availability) {
+                        (false, _) => None,
+                        (true, Availability::Yes) => Some("is-success is-selected"),
+                        (true, Availability::No) => Some("is-danger is-selected"),
+                        (true, Availability::Unknown) => Some("is-dark is-selected"),
+                    };
+                    html!(
+                        <button class={classes!("button", "is-small", selected_class)} {onclick}>
+                            {availability.to_string()}
+                        </button>
+                    )
+                })
+                .collect::<Html>();
+            html!(
+                <div class="is-flex is-align-items-center is-justify-content-space-between mb-2">
+                    <span class="icon-text">
+                        <span class="icon">
+                            <i class={classes!("fas", get_accessibility_fontawesome_icon(feature))}></i>
+                        </span>
+                        <span>{feature.to_string()}</span>
+                    </span>
+                    <div class="buttons has-addons mb-0">{buttons}</div>
+                </div>
+            )
+        })
+        .collect::<Html>();
+
+    html!(
+        <div class="field container is-max-tablet">
+            <div class="label">{t!("location-definer-accessibility-label")}</div>
+            {rows}
+        </div>
+    )
+}
@@ -0,0 +1,101 @@
+/*
+Copyright (C) 2025 Mateusz Mazur (Mazurel) <mateusz.mazur@e.email>
+
+This program is free software; you can redistribute it and/or
+modify it under the terms of the GNU General Public License
+as published by the Free Software Foundation; either version 2
+of the License, or (at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program; if not, see
+<https://www.gnu.org/licenses/>.
+*/
+
+use super::validation::{issues_of, ValidationMessages};
+use libsopa::address::Address;
+use libsopa::validation::{FieldPath, ValidationIssue};
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+type AddressField = fn(&mut Address) -> &mut String;
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct AddressEditProps {
+    pub address: Address,
+    pub on_address_changed: Callback<Address>,
+    #[prop_or_default]
+    pub issues: Vec<ValidationIssue>,
+}
+
+#[function_component(AddressEdit)]
+pub fn address_edit(props: &AddressEditProps) -> Html {
+    let field_input = |label_key: &str, field: AddressField, path: Option<FieldPath>| -> Html {
+        let issues = path
+            .map(|path| issues_of(&props.issues, &path))
+            .unwrap_or_default();
+        let mut address = props.address.clone();
+        let value = field(&mut address).clone();
+        let onchange = {
+            let on_address_changed = props.on_address_changed.clone();
+            Callback::from(move |event: Event| {
+                if let Some(input) = event.target_dyn_into::<HtmlInputElement>() {
+                    let mut address = address.clone();
+                    *field(&mut address) = input.value().trim().to_string();
+                    // Any edit means that the address was looked at
+                    address.needs_review = false;
+                    on_address_changed.emit(address);
+                }
+            })
+        };
+        html!(
+            <div class="field">
+                <label class="label is-small">{t!(label_key)}</label>
+                <div class="control">
+                    <input class="input" type="text" {value} {onchange}/>
+                </div>
+                <ValidationMessages {issues}/>
+            </div>
+        )
+    };
+
+    html!(
+        <div class="field container is-max-tablet">
+            <div class="label">{t!("location-definer-address-label")}</div>
+            if props.address.needs_review {
+                <div class="notification is-warning is-light">
+                    {t!("address-needs-review")}
+                </div>
+            } else {
+                <ValidationMessages issues={issues_of(&props.issues, &FieldPath::Address)}/>
+            }
+            <div class="columns mb-0">
+                <div class="column is-half">
+                    {field_input("address-street", |address| &mut address.street, None)}
+                </div>
+                <div class="column">
+                    {field_input("address-building", |address| &mut address.building, None)}
+                </div>
+                <div class="column">
+                    {field_input("address-unit", |address| &mut address.unit, None)}
+                </div>
+            </div>
+            <div class="columns mb-0">
+                <div class="column is-one-quarter">
+                    {field_input("address-postal-code", |address| &mut address.postal_code, Some(FieldPath::PostalCode))}
+                </div>
+                <div class="column">
+                    {field_input("address-city", |address| &mut address.city, None)}
+                </div>
+                <div class="column">
+                    {field_input("address-voivodeship-label", |address| &mut address.voivodeship, None)}
+                </div>
+            </div>
+            {field_input("address-remarks", |address| &mut address.remarks, None)}
+        </div>
+    )
+}
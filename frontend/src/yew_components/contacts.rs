@@ -20,7 +20,9 @@ use wasm_bindgen::JsCast;
 use web_sys::{HtmlInputElement, HtmlSelectElement};
 use yew::prelude::*;
 
+use super::validation::{issues_of, ValidationMessages};
 use libsopa::contact::{Contact, ContactMethods, ContactType};
+use libsopa::validation::{FieldPath, ValidationIssue};
 
 fn get_contact_fontawesome_icon(contact: ContactType) -> &'static str {
     match contact {
@@ -75,6 +77,8 @@ pub fn contact_view(props: &ContactViewProps) -> Html {
 pub struct ContactEditProps {
     pub contact: Contact,
     pub update_contact_cb: Callback<Option<Contact>>,
+    #[prop_or_default]
+    pub issues: Vec<ValidationIssue>,
 }
 
 #[function_component(ContactEdit)]
@@ -174,6 +178,7 @@ pub fn contact_edit(props: &ContactEditProps) -> Html {
                                 value={props.contact.value.clone()}
                                 {onchange}
                                 />
+                            <ValidationMessages issues={props.issues.clone()}/>
                         </div>
                         <div>
                             <button class="icon m-3" onclick={remove_self_cb}>
@@ -205,6 +210,8 @@ pub fn contacts_view(props: &ContactsViewProps) -> Html {
 pub struct ContactsEditProps {
     pub methods: ContactMethods,
     pub on_methods_changed: Callback<ContactMethods>,
+    #[prop_or_default]
+    pub issues: Vec<ValidationIssue>,
 }
 
 #[function_component(ContactMethodsEdit)]
@@ -299,7 +306,8 @@ pub fn contacts_view(props: &ContactsEditProps) -> Html {
                     on_methods_changed.emit(new_contact_methods);
                 }
             });
-            html!(<ContactEdit contact={contact_method.clone()} {update_contact_cb}/>)
+            let issues = issues_of(&props.issues, &FieldPath::Contact(i));
+            html!(<ContactEdit contact={contact_method.clone()} {update_contact_cb} {issues}/>)
         })
         .collect();
 
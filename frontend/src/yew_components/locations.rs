@@ -16,9 +16,14 @@ along with this program; if not, see
 <https://www.gnu.org/licenses/>.
 */
 
+use super::accessibility::AccessibilityView;
 use super::opened_hours::OpenedHoursView;
+use super::service_terms::ServiceTermsView;
 use crate::yew_components::{ContactMethodsView, TagSelectionType, TagView};
+use libsopa::address::Address;
 use libsopa::locations::Location;
+use libsopa::organisations::Organisation;
+use libsopa::ranking::RankingExplanation;
 use libsopa::tags::{get_all_supported_tags, Tags};
 use yew::prelude::*;
 
@@ -48,13 +53,17 @@ pub fn location_description_view(props: &LocationDescriptionViewProps) -> Html {
 
 #[derive(Clone, PartialEq, Properties)]
 pub struct LocationAddressViewProps {
-    address: String,
+    address: Address,
 }
 
 #[function_component(LocationAddressView)]
 pub fn location_address_view(props: &LocationAddressViewProps) -> Html {
-    let address = props.address.trim();
-    let is_address_empty = address.is_empty();
+    let address_lines = props.address.lines();
+    let is_address_empty = address_lines.is_empty();
+    let address = address_lines
+        .into_iter()
+        .map(|line| html!(<>{line}<br/></>))
+        .collect::<Html>();
 
     html!(
         if !is_address_empty {
@@ -83,6 +92,53 @@ pub struct LocationViewProps {
     pub global_selected_tags: Option<Tags>,
     #[prop_or(None)]
     pub selection_settings: Option<SelectionSettings>,
+    #[prop_or(None)]
+    pub ranking_explanation: Option<RankingExplanation>,
+    /// Organisation the location is a branch of.
+    #[prop_or(None)]
+    pub organisation: Option<Organisation>,
+    #[prop_or_default]
+    pub other_branches: Vec<Location>,
+}
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct OtherBranchesViewProps {
+    organisation: Organisation,
+    other_branches: Vec<Location>,
+}
+
+#[function_component(OtherBranchesView)]
+pub fn other_branches_view(props: &OtherBranchesViewProps) -> Html {
+    let branches = props
+        .other_branches
+        .iter()
+        .map(|branch| {
+            let address = branch.address.lines().join(", ");
+            html!(
+                <li>
+                    <strong>{branch.name.to_string()}</strong>
+                    if !address.is_empty() {
+                        {" — "}{address}
+                    }
+                </li>
+            )
+        })
+        .collect::<Html>();
+
+    html!(
+        <div class="card-content">
+            <div class="icon-text">
+                <span class="icon has-text-info">
+                    <i class="fas fa-sitemap"></i>
+                </span>
+                <span>{t!("location-branch-of", organisation = props.organisation.name)}</span>
+            </div>
+            if !props.other_branches.is_empty() {
+                <p class="mt-2">{t!("location-other-branches")}</p>
+                <ul class="ml-5">{branches}</ul>
+            }
+        </div>
+    )
 }
 
 fn get_matching_tags(all_tags: &Tags, my_tags: &Tags) -> Vec<Html> {
@@ -111,7 +167,7 @@ fn get_matching_tags(all_tags: &Tags, my_tags: &Tags) -> Vec<Html> {
 #[function_component(LocationView)]
 pub fn location_view(props: &LocationViewProps) -> Html {
     let location = &props.location;
-    let description = location.description.clone();
+    let description = location.description.to_string();
     let is_selectable = props.selection_settings.is_some();
 
     let tag_elements = match &props.global_selected_tags {
@@ -119,6 +175,38 @@ pub fn location_view(props: &LocationViewProps) -> Html {
         Some(selected_tags) => Some(get_matching_tags(selected_tags, &props.location.tags)),
     };
 
+    let ranking_summary = match &props.ranking_explanation {
+        Some(explanation) if explanation.needs_count > 0 => {
+            let missing_tags = explanation
+                .missing
+                .iter()
+                .map(|tag| tag.human_readable().to_string())
+                .collect::<Vec<_>>()
+                .join(", ");
+            html!(
+                <div class="card-header-icon" title={missing_tags}>
+                    <span class="tag is-info is-light is-medium">
+                        {t!(
+                            "location-matches-needs",
+                            matched = explanation.matched_needs_count,
+                            needs = explanation.needs_count
+                        )}
+                    </span>
+                </div>
+            )
+        }
+        _ => html!(),
+    };
+
+    let verification_info = match location.last_verified_at {
+        Some(verified_at) => html!(
+            <p class="card-content py-0 is-size-7 has-text-grey">
+                {t!("location-verified-on", date = verified_at)}
+            </p>
+        ),
+        None => html!(),
+    };
+
     let onclick = {
         let selection_settings = props.selection_settings.clone();
         let location = location.clone();
@@ -148,20 +236,27 @@ pub fn location_view(props: &LocationViewProps) -> Html {
             <div class="location card">
                 <div class="card-header">
                     <div class="card-header-title has-text-dark is-size-4 is-capitalized">
-                        {location.name.clone()}
+                        {location.name.to_string()}
                     </div>
+                    {ranking_summary}
                 </div>
                 if !props.simplified_view {
                     <LocationDescriptionView {description} />
+                    <ServiceTermsView service_terms={location.service_terms.clone()} />
+                    {verification_info}
                     <div class="columns">
                         <div class="column">
                             <LocationAddressView address={location.address.clone()} />
                             <ContactMethodsView methods={location.contact_methods.clone()} />
+                            <AccessibilityView accessibility={location.accessibility.clone()} />
                         </div>
                         <div class="column">
                             <OpenedHoursView opened_hours={location.opened_hours.clone()} />
                         </div>
                     </div>
+                    if let Some(organisation) = props.organisation.clone() {
+                        <OtherBranchesView {organisation} other_branches={props.other_branches.clone()} />
+                    }
                     if let Some(tag_elements) = tag_elements {
                         <div class="card-content">
                             <div class="component">
@@ -16,13 +16,17 @@ along with this program; if not, see
 <https://www.gnu.org/licenses/>.
 */
 
+pub mod accessibility;
+pub mod address;
 mod contacts;
 pub mod day_hours;
 mod locations;
 pub mod navigation;
 pub mod opened_hours;
+pub mod service_terms;
 mod tags;
 pub mod timepicker;
+pub mod validation;
 
 pub use contacts::*;
 pub use locations::*;
@@ -17,13 +17,17 @@ along with this program; if not, see
 */
 
 use super::day_hours::DayHoursEdit;
+use super::validation::{issues_of, ValidationMessages};
 use libsopa::time::{Day, OpenedHours, TimeSpan};
+use libsopa::validation::{FieldPath, ValidationIssue};
 use yew::prelude::*;
 
 #[derive(Properties, Clone, PartialEq)]
 pub struct OpenedHoursEditProps {
     pub opened_hours: OpenedHours,
     pub on_opened_hours_changed: Callback<OpenedHours>,
+    #[prop_or_default]
+    pub issues: Vec<ValidationIssue>,
 }
 
 #[function_component(OpenedHoursEdit)]
@@ -54,12 +58,16 @@ pub fn opened_hours_edit(props: &OpenedHoursEditProps) -> Html {
                 })
             };
 
+            let issues = issues_of(&props.issues, &FieldPath::OpenedHours(day.clone()));
             html! {
-                <DayHoursEdit
-                    day={day}
-                    time_span={time_span}
-                    on_time_span_changed={on_time_span_changed}
-                />
+                <>
+                    <DayHoursEdit
+                        day={day}
+                        time_span={time_span}
+                        on_time_span_changed={on_time_span_changed}
+                    />
+                    <ValidationMessages {issues}/>
+                </>
             }
         })
         .collect();
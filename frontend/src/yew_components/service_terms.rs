@@ -0,0 +1,219 @@
+/*
+Copyright (C) 2025 Mateusz Mazur (Mazurel) <mateusz.mazur@e.email>
+
+This program is free software; you can redistribute it and/or
+modify it under the terms of the GNU General Public License
+as published by the Free Software Foundation; either version 2
+of the License, or (at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program; if not, see
+<https://www.gnu.org/licenses/>.
+*/
+
+use super::validation::{issues_of, ValidationMessages};
+use libsopa::audit;
+use libsopa::service_terms::{CostKind, ReferralRequirement, ServiceTerms, WaitingTime};
+use libsopa::validation::{FieldPath, ValidationIssue};
+use web_sys::{HtmlInputElement, HtmlSelectElement};
+use yew::prelude::*;
+
+type AgeField = fn(&mut ServiceTerms) -> &mut Option<u8>;
+
+#[derive(Properties, PartialEq)]
+pub struct ServiceTermsViewProps {
+    pub service_terms: ServiceTerms,
+}
+
+/// Known terms as tags, the ones in favour of the person seeking help are highlighted.
+#[function_component(ServiceTermsView)]
+pub fn service_terms_view(props: &ServiceTermsViewProps) -> Html {
+    let terms = &props.service_terms;
+    let mut tags: Vec<(String, &str)> = vec![];
+    if terms.cost != CostKind::Unknown {
+        let class = match terms.cost {
+            CostKind::Free => "is-success",
+            _ => "is-light",
+        };
+        tags.push((terms.cost.to_string(), class));
+    }
+    if terms.referral != ReferralRequirement::Unknown {
+        let class = match terms.referral {
+            ReferralRequirement::NotRequired => "is-success",
+            _ => "is-warning",
+        };
+        tags.push((terms.referral.to_string(), class));
+    }
+    if !terms.eligibility.is_empty() {
+        tags.push((terms.eligibility.to_string(), "is-info"));
+    }
+    if let Some(waiting_time) = terms.waiting_time {
+        tags.push((waiting_time.to_string(), "is-light"));
+    }
+
+    html!(
+        if !tags.is_empty() {
+            <div class="tags ml-6 mt-2">
+                {tags.into_iter().map(|(text, class)| html!(
+                    <span class={classes!("tag", "is-medium", class)}>{text}</span>
+                )).collect::<Html>()}
+            </div>
+        }
+    )
+}
+
+#[derive(Properties, PartialEq)]
+pub struct ServiceTermsEditProps {
+    pub service_terms: ServiceTerms,
+    pub on_service_terms_changed: Callback<ServiceTerms>,
+    #[prop_or_default]
+    pub issues: Vec<ValidationIssue>,
+}
+
+#[function_component(ServiceTermsEdit)]
+pub fn service_terms_edit(props: &ServiceTermsEditProps) -> Html {
+    let terms = &props.service_terms;
+    let emit_changed = |modify_fn: fn(&mut ServiceTerms, String)| {
+        let on_service_terms_changed = props.on_service_terms_changed.clone();
+        let terms = terms.clone();
+        move |value: String| {
+            let mut terms = terms.clone();
+            modify_fn(&mut terms, value);
+            on_service_terms_changed.emit(terms);
+        }
+    };
+    let on_select_changed = |modify_fn: fn(&mut ServiceTerms, String)| {
+        let emit = emit_changed(modify_fn);
+        Callback::from(move |event: Event| {
+            if let Some(select) = event.target_dyn_into::<HtmlSelectElement>() {
+                emit(select.value());
+            }
+        })
+    };
+    let on_input_changed = |modify_fn: fn(&mut ServiceTerms, String)| {
+        let emit = emit_changed(modify_fn);
+        Callback::from(move |event: Event| {
+            if let Some(input) = event.target_dyn_into::<HtmlInputElement>() {
+                emit(input.value());
+            }
+        })
+    };
+
+    let cost_options = CostKind::ALL
+        .into_iter()
+        .map(|cost| {
+            html!(<option value={cost.key()} selected={terms.cost == cost}>{cost.to_string()}</option>)
+        })
+        .collect::<Html>();
+    let referral_options = ReferralRequirement::ALL
+        .into_iter()
+        .map(|referral| {
+            html!(
+                <option value={referral.key()} selected={terms.referral == referral}>
+                    {referral.to_string()}
+                </option>
+            )
+        })
+        .collect::<Html>();
+
+    let age_input = |label_key: &str, field: AgeField| -> Html {
+        let mut terms = terms.clone();
+        let value = field(&mut terms)
+            .map(|age| age.to_string())
+            .unwrap_or_default();
+        let onchange = {
+            let on_service_terms_changed = props.on_service_terms_changed.clone();
+            Callback::from(move |event: Event| {
+                if let Some(input) = event.target_dyn_into::<HtmlInputElement>() {
+                    let mut terms = terms.clone();
+                    *field(&mut terms) = input.value().trim().parse().ok();
+                    on_service_terms_changed.emit(terms);
+                }
+            })
+        };
+        html!(
+            <div class="field">
+                <label class="label is-small">{t!(label_key)}</label>
+                <div class="control">
+                    <input class="input" type="number" min="0" max="255" {value} {onchange}/>
+                </div>
+            </div>
+        )
+    };
+
+    let waiting_days = terms
+        .waiting_time
+        .map(|waiting_time| waiting_time.days.to_string())
+        .unwrap_or_default();
+
+    html!(
+        <div class="field container is-max-tablet">
+            <div class="label">{t!("location-definer-service-terms-label")}</div>
+            <div class="columns mb-0">
+                <div class="column">
+                    <label class="label is-small">{t!("service-terms-cost")}</label>
+                    <div class="select is-fullwidth">
+                        <select onchange={on_select_changed(|terms, key| {
+                            terms.cost = CostKind::from_key(&key).unwrap_or_default()
+                        })}>
+                            {cost_options}
+                        </select>
+                    </div>
+                </div>
+                <div class="column">
+                    <label class="label is-small">{t!("service-terms-referral")}</label>
+                    <div class="select is-fullwidth">
+                        <select onchange={on_select_changed(|terms, key| {
+                            terms.referral = ReferralRequirement::from_key(&key).unwrap_or_default()
+                        })}>
+                            {referral_options}
+                        </select>
+                    </div>
+                </div>
+            </div>
+            <ValidationMessages issues={issues_of(&props.issues, &FieldPath::Eligibility)}/>
+            <div class="columns mb-0">
+                <div class="column is-one-quarter">
+                    {age_input("service-terms-min-age", |terms| &mut terms.eligibility.min_age)}
+                </div>
+                <div class="column is-one-quarter">
+                    {age_input("service-terms-max-age", |terms| &mut terms.eligibility.max_age)}
+                </div>
+                <div class="column">
+                    <div class="field">
+                        <label class="label is-small">{t!("service-terms-residency")}</label>
+                        <div class="control">
+                            <input class="input" type="text"
+                                value={terms.eligibility.residency.clone()}
+                                placeholder={t!("service-terms-residency-placeholder")}
+                                onchange={on_input_changed(|terms, residency| {
+                                    terms.eligibility.residency = residency.trim().to_string()
+                                })}/>
+                        </div>
+                    </div>
+                </div>
+            </div>
+            <div class="field">
+                <label class="label is-small">{t!("service-terms-waiting-days")}</label>
+                <div class="control">
+                    // Every change of the estimate is dated anew
+                    <input class="input" type="number" min="0" value={waiting_days}
+                        onchange={on_input_changed(|terms, days| {
+                            terms.waiting_time = days.trim().parse().ok().map(|days| WaitingTime {
+                                days,
+                                as_of: audit::now(),
+                            })
+                        })}/>
+                </div>
+                if let Some(waiting_time) = terms.waiting_time {
+                    <p class="help">{t!("service-terms-waiting-as-of", date = waiting_time.as_of)}</p>
+                }
+            </div>
+        </div>
+    )
+}
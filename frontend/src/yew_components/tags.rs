@@ -24,6 +24,28 @@ use libsopa::tags::Tag;
 pub enum TagSelectionType {
     Acceptable,
     NonAcceptable,
+    // States below are only used by `tri_state` tags
+    Unselected,
+    Required,
+    Excluded,
+    /// Not selected, but suggested to the user, e.g. inferred from the address
+    Suggested,
+}
+
+impl TagSelectionType {
+    /// State after the tag is clicked. Tri-state tags cycle through
+    /// unselected -> acceptable (preferred) -> required -> excluded.
+    fn next(self, tri_state: bool) -> Self {
+        use TagSelectionType::*;
+        match (tri_state, self) {
+            (false, Acceptable) => NonAcceptable,
+            (false, _) => Acceptable,
+            (true, Unselected) => Acceptable,
+            (true, Acceptable) => Required,
+            (true, Required) => Excluded,
+            (true, _) => Unselected,
+        }
+    }
 }
 
 #[derive(Properties, PartialEq)]
@@ -35,6 +57,8 @@ pub struct TagViewProps {
     pub interactive: bool,
     #[prop_or(None)]
     pub selection_changed: Option<Callback<(Tag, TagSelectionType)>>,
+    #[prop_or(false)]
+    pub tri_state: bool,
 }
 
 fn tag_selection_into_html_style(selection_state: TagSelectionType) -> Classes {
@@ -48,6 +72,21 @@ fn tag_selection_into_html_style(selection_state: TagSelectionType) -> Classes {
             classes.push("is-danger".to_string());
             classes.push("has-background-danger-light".to_string());
         }
+        TagSelectionType::Unselected => {
+            classes.push("is-light".to_string());
+        }
+        TagSelectionType::Required => {
+            classes.push("is-link".to_string());
+            classes.push("is-required".to_string());
+        }
+        TagSelectionType::Excluded => {
+            classes.push("is-dark".to_string());
+            classes.push("is-excluded".to_string());
+        }
+        TagSelectionType::Suggested => {
+            classes.push("is-warning".to_string());
+            classes.push("is-suggested".to_string());
+        }
     }
 
     classes.append(&mut vec![
@@ -66,8 +105,6 @@ fn tag_selection_into_html_style(selection_state: TagSelectionType) -> Classes {
 
 #[function_component(TagView)]
 pub fn selectable_tag(props: &TagViewProps) -> Html {
-    use TagSelectionType::*;
-
     let tag_selection_state = use_state_eq(|| props.selection_type);
 
     {
@@ -82,11 +119,9 @@ pub fn selectable_tag(props: &TagViewProps) -> Html {
         let tag_selection_state = tag_selection_state.clone();
         let selection_changed = props.selection_changed.clone();
         let tag = props.tag.clone();
+        let tri_state = props.tri_state;
         Callback::from(move |_| {
-            let next_selection_state = match *tag_selection_state {
-                Acceptable => NonAcceptable,
-                NonAcceptable => Acceptable,
-            };
+            let next_selection_state = tag_selection_state.next(tri_state);
             tag_selection_state.set(next_selection_state.clone());
 
             if let Some(selection_changed) = selection_changed.clone() {
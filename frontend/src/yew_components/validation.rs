@@ -0,0 +1,50 @@
+/*
+Copyright (C) 2025 Mateusz Mazur (Mazurel) <mateusz.mazur@e.email>
+
+This program is free software; you can redistribute it and/or
+modify it under the terms of the GNU General Public License
+as published by the Free Software Foundation; either version 2
+of the License, or (at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program; if not, see
+<https://www.gnu.org/licenses/>.
+*/
+
+use libsopa::validation::{FieldPath, Severity, ValidationIssue};
+use yew::prelude::*;
+
+/// Issues that refer to the `field`.
+pub fn issues_of(issues: &[ValidationIssue], field: &FieldPath) -> Vec<ValidationIssue> {
+    issues
+        .iter()
+        .filter(|issue| issue.field == *field)
+        .cloned()
+        .collect()
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct ValidationMessagesProps {
+    pub issues: Vec<ValidationIssue>,
+}
+
+/// Inline messages, to be put below the offending input.
+#[function_component(ValidationMessages)]
+pub fn validation_messages(props: &ValidationMessagesProps) -> Html {
+    props
+        .issues
+        .iter()
+        .map(|issue| {
+            let class = match issue.severity() {
+                Severity::Error => "help is-danger",
+                Severity::Warning => "help is-warning",
+            };
+            html!(<p {class}>{issue.kind.to_string()}</p>)
+        })
+        .collect()
+}
@@ -0,0 +1,194 @@
+/*
+Copyright (C) 2025 Mateusz Mazur (Mazurel) <mateusz.mazur@e.email>
+
+This program is free software; you can redistribute it and/or
+modify it under the terms of the GNU General Public License
+as published by the Free Software Foundation; either version 2
+of the License, or (at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program; if not, see
+<https://www.gnu.org/licenses/>.
+*/
+
+//! Accessibility of locations for people with disabilities.
+
+use crate::locations::Location;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Whether a location offers an accessibility feature.
+/// Most locations were never asked, so `Unknown` is kept apart from `No`.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Availability {
+    Yes,
+    No,
+    #[default]
+    Unknown,
+}
+
+impl Availability {
+    pub const ALL: [Availability; 3] = [Availability::Yes, Availability::No, Availability::Unknown];
+}
+
+impl fmt::Display for Availability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let availability = match self {
+            Availability::Yes => t!("availability-yes"),
+            Availability::No => t!("availability-no"),
+            Availability::Unknown => t!("availability-unknown"),
+        };
+        write!(f, "{availability}")
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AccessibilityFeature {
+    /// Entrance without stairs or with a ramp or a lift.
+    StepFreeAccess,
+    AccessibleToilet,
+    /// Hearing loop for people using hearing aids.
+    InductionLoop,
+    /// Polish Sign Language (PJM) interpreter.
+    SignLanguageInterpreter,
+    /// Materials in easy-to-read language.
+    EasyRead,
+}
+
+impl AccessibilityFeature {
+    pub const ALL: [AccessibilityFeature; 5] = [
+        AccessibilityFeature::StepFreeAccess,
+        AccessibilityFeature::AccessibleToilet,
+        AccessibilityFeature::InductionLoop,
+        AccessibilityFeature::SignLanguageInterpreter,
+        AccessibilityFeature::EasyRead,
+    ];
+
+    /// Name used in queries, e.g. `accessible:step-free`.
+    pub fn key(&self) -> &'static str {
+        match self {
+            AccessibilityFeature::StepFreeAccess => "step-free",
+            AccessibilityFeature::AccessibleToilet => "toilet",
+            AccessibilityFeature::InductionLoop => "induction-loop",
+            AccessibilityFeature::SignLanguageInterpreter => "pjm",
+            AccessibilityFeature::EasyRead => "easy-read",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<AccessibilityFeature> {
+        Self::ALL.into_iter().find(|feature| feature.key() == key)
+    }
+}
+
+impl fmt::Display for AccessibilityFeature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let feature = match self {
+            AccessibilityFeature::StepFreeAccess => t!("accessibility-step-free-access"),
+            AccessibilityFeature::AccessibleToilet => t!("accessibility-accessible-toilet"),
+            AccessibilityFeature::InductionLoop => t!("accessibility-induction-loop"),
+            AccessibilityFeature::SignLanguageInterpreter => {
+                t!("accessibility-sign-language-interpreter")
+            }
+            AccessibilityFeature::EasyRead => t!("accessibility-easy-read"),
+        };
+        write!(f, "{feature}")
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Accessibility {
+    #[serde(default)]
+    pub step_free_access: Availability,
+    #[serde(default)]
+    pub accessible_toilet: Availability,
+    #[serde(default)]
+    pub induction_loop: Availability,
+    #[serde(default)]
+    pub sign_language_interpreter: Availability,
+    #[serde(default)]
+    pub easy_read: Availability,
+}
+
+impl Accessibility {
+    pub fn get(&self, feature: AccessibilityFeature) -> Availability {
+        match feature {
+            AccessibilityFeature::StepFreeAccess => self.step_free_access,
+            AccessibilityFeature::AccessibleToilet => self.accessible_toilet,
+            AccessibilityFeature::InductionLoop => self.induction_loop,
+            AccessibilityFeature::SignLanguageInterpreter => self.sign_language_interpreter,
+            AccessibilityFeature::EasyRead => self.easy_read,
+        }
+    }
+
+    pub fn set(&mut self, feature: AccessibilityFeature, availability: Availability) {
+        let field = match feature {
+            AccessibilityFeature::StepFreeAccess => &mut self.step_free_access,
+            AccessibilityFeature::AccessibleToilet => &mut self.accessible_toilet,
+            AccessibilityFeature::InductionLoop => &mut self.induction_loop,
+            AccessibilityFeature::SignLanguageInterpreter => &mut self.sign_language_interpreter,
+            AccessibilityFeature::EasyRead => &mut self.easy_read,
+        };
+        *field = availability;
+    }
+
+    /// Features with known availability, in the order of `AccessibilityFeature::ALL`.
+    pub fn known_features(&self) -> Vec<(AccessibilityFeature, Availability)> {
+        AccessibilityFeature::ALL
+            .into_iter()
+            .map(|feature| (feature, self.get(feature)))
+            .filter(|(_, availability)| *availability != Availability::Unknown)
+            .collect()
+    }
+}
+
+impl Location {
+    /// Whether all `features` are confirmed to be available,
+    /// unknown availability does not count.
+    pub fn offers_accessibility(&self, features: &[AccessibilityFeature]) -> bool {
+        features
+            .iter()
+            .all(|feature| self.accessibility.get(*feature) == Availability::Yes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_confirmed_features_are_offered() {
+        let mut location = Location::default();
+        location
+            .accessibility
+            .set(AccessibilityFeature::StepFreeAccess, Availability::Yes);
+        location
+            .accessibility
+            .set(AccessibilityFeature::InductionLoop, Availability::No);
+
+        assert!(location.offers_accessibility(&[AccessibilityFeature::StepFreeAccess]));
+        assert!(!location.offers_accessibility(&[
+            AccessibilityFeature::StepFreeAccess,
+            AccessibilityFeature::EasyRead
+        ]));
+        assert_eq!(
+            location.accessibility.known_features(),
+            vec![
+                (AccessibilityFeature::StepFreeAccess, Availability::Yes),
+                (AccessibilityFeature::InductionLoop, Availability::No),
+            ]
+        );
+    }
+
+    #[test]
+    fn feature_keys_round_trip() {
+        for feature in AccessibilityFeature::ALL {
+            assert_eq!(AccessibilityFeature::from_key(feature.key()), Some(feature));
+        }
+        assert_eq!(AccessibilityFeature::from_key("stairs"), None);
+    }
+}
@@ -0,0 +1,358 @@
+/*
+Copyright (C) 2025 Mateusz Mazur (Mazurel) <mateusz.mazur@e.email>
+
+This program is free software; you can redistribute it and/or
+modify it under the terms of the GNU General Public License
+as published by the Free Software Foundation; either version 2
+of the License, or (at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program; if not, see
+<https://www.gnu.org/licenses/>.
+*/
+
+//! Structured postal address, with a parser of the Polish free-form addresses,
+//! e.g. `ul. Długa 5/3, 80-001 Gdańsk`.
+
+use serde::{Deserialize, Deserializer, Serialize};
+use std::fmt;
+
+/// Prefixes of the unit number, e.g. "lok. 3" or "m. 3".
+const UNIT_PREFIXES: &[&str] = &["lok.", "m."];
+const VOIVODESHIP_PREFIXES: &[&str] = &["woj.", "województwo"];
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct Address {
+    /// Street name as written, with its prefix, e.g. "ul. Długa" or "al. Grunwaldzka".
+    pub street: String,
+    pub building: String,
+    pub unit: String,
+    /// Polish postal code, e.g. "80-001".
+    pub postal_code: String,
+    pub city: String,
+    pub voivodeship: String,
+    /// Anything that does not fit other fields, e.g. "entrance from the yard".
+    pub remarks: String,
+    /// Set for addresses that could not be fully parsed from the free-form text,
+    /// e.g. lacking a postal code, unrecognised parts are kept in the `remarks`.
+    #[serde(default)]
+    pub needs_review: bool,
+}
+
+pub fn is_postal_code(text: &str) -> bool {
+    let bytes = text.as_bytes();
+    bytes.len() == 6
+        && bytes[2] == b'-'
+        && bytes
+            .iter()
+            .enumerate()
+            .all(|(i, b)| i == 2 || b.is_ascii_digit())
+}
+
+/// Splits "<postal code> <city>", also accepting spaces and an en dash
+/// around the hyphen, e.g. "80 – 432 Gdańsk". Postal code is normalised to "80-432".
+fn split_postal_code(part: &str) -> Option<(String, &str)> {
+    let (region, rest) = part.split_at_checked(2)?;
+    let rest = rest.trim_start().strip_prefix(['-', '–'])?.trim_start();
+    let (office, city) = rest.split_at_checked(3)?;
+    let postal_code = format!("{region}-{office}");
+    let is_separated = city.is_empty() || city.starts_with(char::is_whitespace);
+    (is_postal_code(&postal_code) && is_separated).then(|| (postal_code, city.trim()))
+}
+
+fn is_building_number(text: &str) -> bool {
+    text.starts_with(|c: char| c.is_ascii_digit())
+        && text
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '/' || c == '-')
+}
+
+fn is_building_letter(text: &str) -> bool {
+    let mut chars = text.chars();
+    chars.next().is_some_and(char::is_alphabetic) && chars.next().is_none()
+}
+
+fn strip_any_prefix<'a>(text: &'a str, prefixes: &[&str]) -> Option<&'a str> {
+    prefixes.iter().find_map(|prefix| {
+        let (head, rest) = (text.get(..prefix.len())?, text.get(prefix.len()..)?);
+        let rest = rest.trim();
+        (head.to_lowercase() == *prefix && !rest.is_empty()).then_some(rest)
+    })
+}
+
+impl Address {
+    /// Parses free-form address, returns `None` if it lacks
+    /// a street with a building number, or a postal code with a city.
+    ///
+    /// ```
+    /// # use libsopa::address::Address;
+    /// let address = Address::parse("ul. Długa 5/3, 80-001 Gdańsk").unwrap();
+    /// assert_eq!(address.street, "ul. Długa");
+    /// assert_eq!(address.unit, "3");
+    /// assert_eq!(address.city, "Gdańsk");
+    /// ```
+    pub fn parse(text: &str) -> Option<Address> {
+        let address = Address::parse_recognised(text);
+        address.is_complete().then_some(address)
+    }
+
+    /// Fields recognised in the free-form `text`, anything else is put in the remarks.
+    fn parse_recognised(text: &str) -> Address {
+        let mut address = Address::default();
+        let mut remarks = vec![];
+        for part in text.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            if address.postal_code.is_empty() {
+                if let Some((postal_code, city)) = split_postal_code(part) {
+                    address.postal_code = postal_code;
+                    if !city.is_empty() {
+                        let previous_city = std::mem::replace(&mut address.city, city.to_string());
+                        if !previous_city.is_empty() {
+                            remarks.push(previous_city);
+                        }
+                    }
+                    continue;
+                }
+            }
+            if address.voivodeship.is_empty() {
+                if let Some(voivodeship) = strip_any_prefix(part, VOIVODESHIP_PREFIXES) {
+                    address.voivodeship = voivodeship.to_string();
+                    continue;
+                }
+            }
+            if address.building.is_empty() {
+                if let Some(city) = address.parse_street(part) {
+                    match (city.is_empty(), address.city.is_empty()) {
+                        (true, _) => {}
+                        (false, true) => address.city = city,
+                        (false, false) => remarks.push(city),
+                    }
+                    continue;
+                }
+            }
+            if address.unit.is_empty() && !address.building.is_empty() {
+                if let Some(unit) = strip_any_prefix(part, UNIT_PREFIXES) {
+                    address.unit = unit.to_string();
+                    continue;
+                }
+            }
+            remarks.push(part.to_string());
+        }
+        address.remarks = remarks.join(", ");
+        address
+    }
+
+    fn is_complete(&self) -> bool {
+        !self.building.is_empty() && !self.postal_code.is_empty()
+    }
+
+    /// Parses "<street> <building>[ <letter>][/<unit>] [lok. <unit>] [<city>]",
+    /// returns the city written after the building, empty if there was none.
+    fn parse_street(&mut self, part: &str) -> Option<String> {
+        let mut words: Vec<&str> = part.split_whitespace().collect();
+        let mut unit = "";
+        if let [.., prefix, unit_number] = words[..] {
+            if UNIT_PREFIXES.contains(&prefix.to_lowercase().as_str()) {
+                unit = unit_number;
+                words.truncate(words.len() - 2);
+            }
+        }
+        // The last number after the street name, e.g. "ul. 10 Lutego 24"
+        let building_idx = (1..words.len())
+            .rev()
+            .find(|idx| is_building_number(words[*idx]))?;
+
+        let (building, building_unit) = match words[building_idx].split_once('/') {
+            Some((building, unit)) => (building, unit),
+            None => (words[building_idx], ""),
+        };
+        let mut building = building.to_string();
+        let mut city_idx = building_idx + 1;
+        // Letter written apart from the number, e.g. "1 A"
+        if let Some(letter) = words.get(city_idx).filter(|word| is_building_letter(word)) {
+            building.push_str(letter);
+            city_idx += 1;
+        }
+        self.street = words[..building_idx].join(" ");
+        self.building = building;
+        self.unit = match building_unit.is_empty() {
+            true => unit.to_string(),
+            false => building_unit.to_string(),
+        };
+        Some(words[city_idx..].join(" "))
+    }
+
+    /// Structured address of the free-form `text`, with the recognised fields filled in.
+    /// Addresses that cannot be fully parsed are flagged for review.
+    pub fn from_free_form(text: &str) -> Address {
+        let mut address = Address::parse_recognised(text);
+        address.needs_review = !address.is_empty() && !address.is_complete();
+        address
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lines().is_empty()
+    }
+
+    /// Street line, city line and remarks, formatted for the current locale.
+    /// Empty lines are skipped.
+    pub fn lines(&self) -> Vec<String> {
+        let mut building = self.building.clone();
+        if !self.unit.is_empty() {
+            building = t!(
+                "address-building-with-unit",
+                building = building,
+                unit = self.unit
+            )
+            .to_string();
+        }
+        let street_line = [self.street.as_str(), building.as_str()]
+            .into_iter()
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ");
+        let city_line = [self.postal_code.as_str(), self.city.as_str()]
+            .into_iter()
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ");
+        let voivodeship_line = match self.voivodeship.is_empty() {
+            true => String::new(),
+            false => t!("address-voivodeship", voivodeship = self.voivodeship).to_string(),
+        };
+
+        [
+            street_line,
+            city_line,
+            voivodeship_line,
+            self.remarks.clone(),
+        ]
+        .into_iter()
+        .filter(|line| !line.trim().is_empty())
+        .collect()
+    }
+
+    /// Accepts both structured address and free-form text stored before
+    /// the address got structured, e.g. in the IndexedDB.
+    pub fn deserialize_structured_or_free_form<'de, D>(deserializer: D) -> Result<Address, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum AnyAddress {
+            Structured(Address),
+            FreeForm(String),
+        }
+        Ok(match AnyAddress::deserialize(deserializer)? {
+            AnyAddress::Structured(address) => address,
+            AnyAddress::FreeForm(text) => Address::from_free_form(&text),
+        })
+    }
+}
+
+/// Address in a single line, formatted for the current locale.
+impl fmt::Display for Address {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.lines().join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn polish_addresses_are_parsed() {
+        let address =
+            Address::parse("al. Grunwaldzka 12A lok. 4, 80-241 Gdańsk, II piętro").unwrap();
+        assert_eq!(
+            address,
+            Address {
+                street: "al. Grunwaldzka".to_string(),
+                building: "12A".to_string(),
+                unit: "4".to_string(),
+                postal_code: "80-241".to_string(),
+                city: "Gdańsk".to_string(),
+                remarks: "II piętro".to_string(),
+                ..Default::default()
+            }
+        );
+
+        let address = Address::parse("81-300 Gdynia, ul. 10 Lutego 24/2, woj. pomorskie").unwrap();
+        assert_eq!(address.street, "ul. 10 Lutego");
+        assert_eq!(address.building, "24");
+        assert_eq!(address.unit, "2");
+        assert_eq!(address.voivodeship, "pomorskie");
+
+        let address = Address::parse("Kolbudy 12, 83-050 Kolbudy").unwrap();
+        assert_eq!(address.street, "Kolbudy");
+        assert_eq!(address.building, "12");
+    }
+
+    #[test]
+    fn unparsable_addresses_are_flagged() {
+        for text in [
+            "Dworzec Główny w Gdańsku",
+            "ul. Długa 5, Gdańsk",
+            "80-001 Gdańsk",
+        ] {
+            assert_eq!(Address::parse(text), None);
+            let address = Address::from_free_form(text);
+            assert!(address.needs_review);
+            assert_eq!(address.to_string(), text);
+        }
+        assert_eq!(Address::from_free_form("  "), Address::default());
+        assert!(Address::default().is_empty());
+
+        let address = Address::from_free_form("ul. Długa 5, 80-001 Gdańsk");
+        assert!(!address.needs_review);
+        assert_eq!(address.to_string(), "ul. Długa 5, 80-001 Gdańsk");
+    }
+
+    #[test]
+    fn partial_addresses_keep_recognised_fields() {
+        let address = Address::from_free_form("80-001 Gdańsk");
+        assert_eq!(address.postal_code, "80-001");
+        assert_eq!(address.city, "Gdańsk");
+        assert_eq!(address.remarks, "");
+
+        let address = Address::from_free_form("ul. Traugutta 2 Gdynia");
+        assert_eq!(address.street, "ul. Traugutta");
+        assert_eq!(address.building, "2");
+        assert_eq!(address.city, "Gdynia");
+        assert!(address.needs_review);
+
+        let address = Address::from_free_form("Dworzec Główny w Gdańsku");
+        assert_eq!(address.remarks, "Dworzec Główny w Gdańsku");
+    }
+
+    #[test]
+    fn postal_codes_with_en_dash_are_normalised() {
+        let address = Address::parse("ul. Długa 5, 80 – 432 Gdańsk").unwrap();
+        assert_eq!(address.postal_code, "80-432");
+        assert_eq!(address.city, "Gdańsk");
+        assert_eq!(
+            Address::parse("ul. Długa 5, 80–432").unwrap().postal_code,
+            "80-432"
+        );
+    }
+
+    #[test]
+    fn building_letter_and_repeated_city_are_kept() {
+        let address = Address::parse("ul. Leczkowa 1 A, 80 – 432 Gdańsk").unwrap();
+        assert_eq!(address.street, "ul. Leczkowa");
+        assert_eq!(address.building, "1A");
+        assert_eq!(address.postal_code, "80-432");
+        assert_eq!(address.city, "Gdańsk");
+        assert_eq!(address.remarks, "");
+
+        let address = Address::parse("ul. Traugutta 2 Gdynia, 81-388 Gdynia Śródmieście").unwrap();
+        assert_eq!(address.city, "Gdynia Śródmieście");
+        assert_eq!(address.remarks, "Gdynia");
+    }
+}
@@ -0,0 +1,193 @@
+/*
+Copyright (C) 2025 Mateusz Mazur (Mazurel) <mateusz.mazur@e.email>
+
+This program is free software; you can redistribute it and/or
+modify it under the terms of the GNU General Public License
+as published by the Free Software Foundation; either version 2
+of the License, or (at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program; if not, see
+<https://www.gnu.org/licenses/>.
+*/
+
+//! Metadata telling when locations were created, updated and verified, and by whom.
+//!
+//! Clock and editor name are set once per thread, which in the browser
+//! means globally, so that `Locations` can maintain the metadata on every update.
+//! Tests running in parallel threads can override them independently.
+
+use crate::locations::Location;
+use serde::{Deserialize, Serialize};
+use std::{
+    cell::{Cell, RefCell},
+    fmt,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+const MILLIS_PER_DAY: i64 = 24 * 60 * 60 * 1000;
+/// Average length of the Gregorian month.
+const MILLIS_PER_MONTH: i64 = 2_629_746_000;
+
+/// Milliseconds since the Unix epoch, in UTC.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Timestamp(pub i64);
+
+impl Timestamp {
+    pub fn months_before(&self, months: u32) -> Timestamp {
+        Timestamp(self.0 - months as i64 * MILLIS_PER_MONTH)
+    }
+
+    /// Year, month and day in UTC, see <http://howardhinnant.github.io/date_algorithms.html>.
+    pub fn date(&self) -> (i64, u32, u32) {
+        let days = self.0.div_euclid(MILLIS_PER_DAY) + 719_468;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days.rem_euclid(146_097);
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        let day = (day_of_year - (153 * shifted_month + 2) / 5 + 1) as u32;
+        let month = if shifted_month < 10 {
+            shifted_month + 3
+        } else {
+            shifted_month - 9
+        } as u32;
+        let year = year_of_era + era * 400 + i64::from(month <= 2);
+        (year, month, day)
+    }
+}
+
+/// Date in the ISO 8601 format, e.g. "2025-03-01".
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (year, month, day) = self.date();
+        write!(f, "{year:04}-{month:02}-{day:02}")
+    }
+}
+
+pub type Clock = fn() -> Timestamp;
+
+fn system_clock() -> Timestamp {
+    let since_epoch = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    Timestamp(since_epoch.as_millis() as i64)
+}
+
+thread_local! {
+    static CLOCK: Cell<Clock> = Cell::new(system_clock);
+    static EDITOR_NAME: RefCell<String> = const { RefCell::new(String::new()) };
+}
+
+/// Replaces the system clock, e.g. on platforms without one.
+pub fn set_clock(clock: Clock) {
+    CLOCK.set(clock);
+}
+
+pub fn now() -> Timestamp {
+    (CLOCK.get())()
+}
+
+/// Sets name written to `Location::updated_by` on every update.
+pub fn set_editor_name(name: &str) {
+    EDITOR_NAME.set(name.trim().to_string());
+}
+
+pub fn editor_name() -> String {
+    EDITOR_NAME.with_borrow(String::clone)
+}
+
+/// Replaces the clock and the editor name until dropped, then restores the previous ones.
+#[cfg(test)]
+pub(crate) struct AuditOverride {
+    clock: Clock,
+    editor_name: String,
+}
+
+#[cfg(test)]
+impl AuditOverride {
+    pub(crate) fn new(clock: Clock, editor_name: &str) -> Self {
+        let previous = AuditOverride {
+            clock: CLOCK.get(),
+            editor_name: self::editor_name(),
+        };
+        set_clock(clock);
+        set_editor_name(editor_name);
+        previous
+    }
+}
+
+#[cfg(test)]
+impl Drop for AuditOverride {
+    fn drop(&mut self) {
+        CLOCK.set(self.clock);
+        EDITOR_NAME.set(std::mem::take(&mut self.editor_name));
+    }
+}
+
+impl Location {
+    /// Records that the location content was checked against reality just now.
+    pub fn mark_verified(&mut self) {
+        self.last_verified_at = Some(now());
+    }
+
+    /// Whether the location was verified at most `months` before `now`.
+    /// Locations never verified are always stale.
+    pub fn is_verified_within(&self, months: u32, now: Timestamp) -> bool {
+        self.last_verified_at
+            .is_some_and(|verified_at| verified_at >= now.months_before(months))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::locations::Locations;
+
+    #[test]
+    fn timestamps_are_shown_as_dates() {
+        assert_eq!(Timestamp(0).to_string(), "1970-01-01");
+        assert_eq!(Timestamp(951_782_400_000).to_string(), "2000-02-29");
+        assert_eq!(Timestamp(1_740_787_200_000).to_string(), "2025-03-01");
+        assert_eq!(Timestamp(-MILLIS_PER_DAY).to_string(), "1969-12-31");
+    }
+
+    #[test]
+    fn metadata_is_maintained_by_locations() {
+        let _audit = AuditOverride::new(|| Timestamp(1_740_787_200_000), " Ala ");
+
+        let mut locations = Locations::new();
+        let mut location = locations.push_new(|loc| loc.name = "Example".into());
+        assert_eq!(location.created_at, Some(Timestamp(1_740_787_200_000)));
+        assert_eq!(location.updated_by, "Ala");
+        assert_eq!(location.last_verified_at, None);
+
+        set_clock(|| Timestamp(1_750_000_000_000));
+        location.mark_verified();
+        let location = locations.push_update(location).unwrap();
+        assert_eq!(location.created_at, Some(Timestamp(1_740_787_200_000)));
+        assert_eq!(location.updated_at, Some(Timestamp(1_750_000_000_000)));
+        assert!(location.is_verified_within(1, now()));
+        let seven_months_later = Timestamp(1_750_000_000_000 + 7 * MILLIS_PER_MONTH);
+        assert!(!location.is_verified_within(6, seven_months_later));
+        assert!(!Location::default().is_verified_within(6, now()));
+    }
+
+    #[test]
+    fn audit_override_is_restored_when_dropped() {
+        let clock_before = now();
+        {
+            let _audit = AuditOverride::new(|| Timestamp(0), "Ala");
+            assert_eq!(now(), Timestamp(0));
+            assert_eq!(editor_name(), "Ala");
+        }
+        assert!(now() >= clock_before);
+        assert_eq!(editor_name(), "");
+    }
+}
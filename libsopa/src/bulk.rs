@@ -0,0 +1,250 @@
+/*
+Copyright (C) 2025 Mateusz Mazur (Mazurel) <mateusz.mazur@e.email>
+
+This program is free software; you can redistribute it and/or
+modify it under the terms of the GNU General Public License
+as published by the Free Software Foundation; either version 2
+of the License, or (at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program; if not, see
+<https://www.gnu.org/licenses/>.
+*/
+
+//! Operations applied at once to many locations, e.g. retagging.
+
+use crate::{
+    address::Address,
+    error::Result,
+    geo::GeoPoint,
+    journal::JournaledLocations,
+    locations::{Location, Locations},
+    query::{Query, QueryContext},
+    tags::Tag,
+    time::OpenedHours,
+};
+use std::collections::HashSet;
+use uuid::Uuid;
+
+/// Locations a bulk operation is applied to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BulkTarget {
+    Matching(Query),
+    Ids(HashSet<Uuid>),
+}
+
+/// New value of a field shared by all the targeted locations.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FieldValue {
+    /// Description in a single locale, other translations are kept.
+    Description {
+        locale: String,
+        text: String,
+    },
+    Address(Address),
+    OpenedHours(OpenedHours),
+    Coordinates(Option<GeoPoint>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BulkAction {
+    AddTag(Tag),
+    RemoveTag(Tag),
+    ReplaceTag {
+        old: Tag,
+        new: Tag,
+    },
+    /// Moves locations to the trash.
+    Delete,
+    SetField(FieldValue),
+}
+
+impl BulkTarget {
+    pub fn contains(&self, location: &Location, context: &QueryContext) -> bool {
+        match self {
+            BulkTarget::Matching(query) => query.matches(location, context),
+            BulkTarget::Ids(ids) => ids.contains(&location.get_id()),
+        }
+    }
+}
+
+impl BulkAction {
+    /// Modified copy of `location`, `None` if the action does not change it.
+    /// `BulkAction::Delete` never modifies locations, see `JournaledLocations::apply_bulk`.
+    pub fn modified(&self, location: &Location) -> Option<Location> {
+        let mut modified = location.clone();
+        match self {
+            BulkAction::AddTag(tag) => {
+                modified.tags.define_tag(&tag.name);
+            }
+            BulkAction::RemoveTag(tag) => {
+                modified.tags.undefine_tag(&tag.name);
+            }
+            BulkAction::ReplaceTag { old, new } => {
+                if location.tags.has_tag(old) {
+                    modified.tags.undefine_tag(&old.name);
+                    modified.tags.define_tag(&new.name);
+                }
+            }
+            BulkAction::Delete => (),
+            BulkAction::SetField(FieldValue::Description { locale, text }) => {
+                modified.description.set(locale, text);
+            }
+            BulkAction::SetField(FieldValue::Address(address)) => {
+                modified.address = address.clone();
+            }
+            BulkAction::SetField(FieldValue::OpenedHours(opened_hours)) => {
+                modified.opened_hours = opened_hours.clone();
+            }
+            BulkAction::SetField(FieldValue::Coordinates(coordinates)) => {
+                modified.coordinates = *coordinates;
+            }
+        }
+        (modified != *location).then_some(modified)
+    }
+}
+
+impl Locations {
+    /// Locations targeted by `target`, ordered by ID. Deleted locations are skipped.
+    pub fn select(&self, target: &BulkTarget, context: &QueryContext) -> Vec<Location> {
+        let mut selected: Vec<Location> = self
+            .locations
+            .values()
+            .filter(|location| !location.is_deleted() && target.contains(location, context))
+            .cloned()
+            .collect();
+        selected.sort_by_key(|location| location.get_id());
+        selected
+    }
+}
+
+impl JournaledLocations<'_> {
+    /// Applies `action` to all locations of `target` as a single operation.
+    /// Returns number of affected locations, those left unchanged are not counted.
+    pub fn apply_bulk(
+        &mut self,
+        target: &BulkTarget,
+        context: &QueryContext,
+        action: &BulkAction,
+    ) -> Result<usize> {
+        let selected = self.select(target, context);
+        self.batch(|journaled| {
+            let mut affected = 0;
+            for location in selected {
+                match action {
+                    BulkAction::Delete => {
+                        journaled.move_to_trash(&location)?;
+                    }
+                    _ => match action.modified(&location) {
+                        Some(modified) => {
+                            journaled.push_update(modified)?;
+                        }
+                        None => continue,
+                    },
+                }
+                affected += 1;
+            }
+            Ok(affected)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{journal::Journal, localized::DEFAULT_CONTENT_LOCALE, tags::Tags};
+
+    fn description(locale: &str, text: &str) -> FieldValue {
+        FieldValue::Description {
+            locale: locale.to_string(),
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn bulk_actions_are_applied_to_target_and_undone_at_once() {
+        let mut locations = Locations::new();
+        let mut journal = Journal::new();
+        let mut journaled = JournaledLocations::new(&mut locations, &mut journal);
+        let mut ids = HashSet::new();
+        for (name, tags) in [
+            ("A", vec!["LGBT", "Gdańsk"]),
+            ("B", vec!["Gdańsk"]),
+            ("C", vec!["Gdynia"]),
+        ] {
+            let location = journaled.push_new(|loc| {
+                loc.name = name.into();
+                loc.tags = Tags::new_tags(tags);
+            });
+            ids.insert(location.get_id());
+        }
+        let context = QueryContext::default();
+        let in_gdansk = BulkTarget::Matching("tag:Gdańsk".parse().unwrap());
+        let lgbt = Tag::new("LGBT".to_string());
+
+        let added = journaled
+            .apply_bulk(&in_gdansk, &context, &BulkAction::AddTag(lgbt.clone()))
+            .unwrap();
+        assert_eq!(added, 1);
+        let with_lgbt = BulkTarget::Matching("tag:LGBT".parse().unwrap());
+        assert_eq!(journaled.select(&with_lgbt, &context).len(), 2);
+
+        let replace = BulkAction::ReplaceTag {
+            old: Tag::new("Gdańsk".to_string()),
+            new: Tag::new("Sopot".to_string()),
+        };
+        let all = BulkTarget::Ids(ids);
+        assert_eq!(journaled.apply_bulk(&all, &context, &replace).unwrap(), 2);
+        assert!(journaled.select(&in_gdansk, &context).is_empty());
+
+        let removed = journaled
+            .apply_bulk(&with_lgbt, &context, &BulkAction::Delete)
+            .unwrap();
+        assert_eq!(removed, 2);
+        assert_eq!(journaled.select(&all, &context).len(), 1);
+
+        journaled.undo();
+        assert_eq!(journaled.select(&all, &context).len(), 3);
+        journaled.undo();
+        assert_eq!(journaled.select(&in_gdansk, &context).len(), 2);
+    }
+
+    #[test]
+    fn set_field_skips_unchanged_locations() {
+        let mut location = Location::default();
+        location.description = "Same".into();
+        let action = BulkAction::SetField(description(DEFAULT_CONTENT_LOCALE, "Same"));
+        assert_eq!(action.modified(&location), None);
+
+        let action = BulkAction::SetField(description(DEFAULT_CONTENT_LOCALE, "Other"));
+        assert_eq!(
+            action
+                .modified(&location)
+                .unwrap()
+                .description
+                .default_text(),
+            "Other"
+        );
+        assert_eq!(BulkAction::Delete.modified(&location), None);
+    }
+
+    #[test]
+    fn set_description_keeps_other_translations() {
+        let mut location = Location::default();
+        location.description.set("pl", "Opis");
+        location.description.set("en", "Description");
+
+        let action = BulkAction::SetField(description("en", "New description"));
+        let modified = action.modified(&location).unwrap();
+        assert_eq!(modified.description.translation("pl"), Some("Opis"));
+        assert_eq!(
+            modified.description.translation("en"),
+            Some("New description")
+        );
+    }
+}
@@ -18,7 +18,7 @@ along with this program; if not, see
 
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, PartialEq, Copy, Clone, Eq, Deserialize, Serialize)]
+#[derive(Debug, PartialEq, Copy, Clone, Eq, Hash, Deserialize, Serialize)]
 pub enum ContactType {
     PhoneNumber,
     Email,
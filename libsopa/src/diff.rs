@@ -0,0 +1,542 @@
+/*
+Copyright (C) 2025 Mateusz Mazur (Mazurel) <mateusz.mazur@e.email>
+
+This program is free software; you can redistribute it and/or
+modify it under the terms of the GNU General Public License
+as published by the Free Software Foundation; either version 2
+of the License, or (at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program; if not, see
+<https://www.gnu.org/licenses/>.
+*/
+
+use crate::{
+    accessibility::{AccessibilityFeature, Availability},
+    contact::Contact,
+    geo::GeoPoint,
+    localized::LocalizedText,
+    locations::{Location, Locations},
+    publication::PublicationState,
+    service_terms::ServiceTerms,
+    tags::Tag,
+    time::{Day, TimeSpan},
+};
+use std::{collections::BTreeMap, fmt};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FieldChange {
+    Name {
+        locale: String,
+        old: String,
+        new: String,
+    },
+    Address {
+        old: String,
+        new: String,
+    },
+    Description {
+        locale: String,
+        old: String,
+        new: String,
+    },
+    TagAdded(Tag),
+    TagRemoved(Tag),
+    ContactAdded(Contact),
+    ContactRemoved(Contact),
+    Coordinates {
+        old: Option<GeoPoint>,
+        new: Option<GeoPoint>,
+    },
+    OpenedHours {
+        day: Day,
+        old: Option<TimeSpan>,
+        new: Option<TimeSpan>,
+    },
+    Accessibility {
+        feature: AccessibilityFeature,
+        old: Availability,
+        new: Availability,
+    },
+    ServiceTerms {
+        old: ServiceTerms,
+        new: ServiceTerms,
+    },
+    /// Location was linked to a different organisation, or unlinked.
+    Organisation {
+        old: Option<Uuid>,
+        new: Option<Uuid>,
+    },
+    PublicationState {
+        old: PublicationState,
+        new: PublicationState,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LocationDiff {
+    pub id: Uuid,
+    /// Name of the newer version of the location.
+    pub name: String,
+    pub changes: Vec<FieldChange>,
+}
+
+/// Difference between older and newer `Locations` snapshot.
+/// Locations moved to the trash are listed as removed,
+/// and the ones restored from it as added.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct LocationsDiff {
+    pub added: Vec<Location>,
+    pub removed: Vec<Location>,
+    pub changed: Vec<LocationDiff>,
+    /// Names of the organisations referred to by `FieldChange::Organisation`.
+    pub organisation_names: BTreeMap<Uuid, String>,
+}
+
+impl LocationsDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+fn diff_text<F>(old: &str, new: &str, into_change: F) -> Option<FieldChange>
+where
+    F: FnOnce(String, String) -> FieldChange,
+{
+    (old != new).then(|| into_change(old.to_string(), new.to_string()))
+}
+
+/// Changes of every translation, see `LocalizedText::translation`.
+fn diff_localized<F>(old: &LocalizedText, new: &LocalizedText, into_change: F) -> Vec<FieldChange>
+where
+    F: Fn(String, String, String) -> FieldChange,
+{
+    let mut locales: Vec<&str> = old.locales().chain(new.locales()).collect();
+    locales.sort();
+    locales.dedup();
+    locales
+        .into_iter()
+        .filter_map(|locale| {
+            let old = old.translation(locale).unwrap_or_default();
+            let new = new.translation(locale).unwrap_or_default();
+            (old != new).then(|| into_change(locale.to_string(), old.to_string(), new.to_string()))
+        })
+        .collect()
+}
+
+impl Location {
+    /// Lists changes of content between `self` and `newer` version of the location.
+    /// Revisions and audit metadata are not taken into account.
+    pub fn diff(&self, newer: &Location) -> Vec<FieldChange> {
+        let mut changes = diff_localized(&self.name, &newer.name, |locale, old, new| {
+            FieldChange::Name { locale, old, new }
+        });
+        changes.extend(diff_text(
+            &self.address.to_string(),
+            &newer.address.to_string(),
+            |old, new| FieldChange::Address { old, new },
+        ));
+        changes.extend(diff_localized(
+            &self.description,
+            &newer.description,
+            |locale, old, new| FieldChange::Description { locale, old, new },
+        ));
+
+        let mut added_tags: Vec<&Tag> = newer
+            .tags
+            .get_all_tags()
+            .into_iter()
+            .filter(|tag| !self.tags.has_tag(tag))
+            .collect();
+        added_tags.sort_by(|t1, t2| t1.name.cmp(&t2.name));
+        let mut removed_tags: Vec<&Tag> = self
+            .tags
+            .get_all_tags()
+            .into_iter()
+            .filter(|tag| !newer.tags.has_tag(tag))
+            .collect();
+        removed_tags.sort_by(|t1, t2| t1.name.cmp(&t2.name));
+        changes.extend(added_tags.into_iter().cloned().map(FieldChange::TagAdded));
+        changes.extend(
+            removed_tags
+                .into_iter()
+                .cloned()
+                .map(FieldChange::TagRemoved),
+        );
+
+        let old_contacts = self.contact_methods.all_contact_methods();
+        let new_contacts = newer.contact_methods.all_contact_methods();
+        changes.extend(
+            new_contacts
+                .iter()
+                .filter(|contact| !old_contacts.contains(contact))
+                .cloned()
+                .map(FieldChange::ContactAdded),
+        );
+        changes.extend(
+            old_contacts
+                .iter()
+                .filter(|contact| !new_contacts.contains(contact))
+                .cloned()
+                .map(FieldChange::ContactRemoved),
+        );
+
+        if self.coordinates != newer.coordinates {
+            changes.push(FieldChange::Coordinates {
+                old: self.coordinates,
+                new: newer.coordinates,
+            });
+        }
+
+        for day in Day::get_all_days_in_week() {
+            let old = self.opened_hours.get_day_time_span(&day);
+            let new = newer.opened_hours.get_day_time_span(&day);
+            if old != new {
+                changes.push(FieldChange::OpenedHours {
+                    day,
+                    old: old.cloned(),
+                    new: new.cloned(),
+                });
+            }
+        }
+
+        for feature in AccessibilityFeature::ALL {
+            let old = self.accessibility.get(feature);
+            let new = newer.accessibility.get(feature);
+            if old != new {
+                changes.push(FieldChange::Accessibility { feature, old, new });
+            }
+        }
+
+        if self.service_terms != newer.service_terms {
+            changes.push(FieldChange::ServiceTerms {
+                old: self.service_terms.clone(),
+                new: newer.service_terms.clone(),
+            });
+        }
+
+        if self.organisation != newer.organisation {
+            changes.push(FieldChange::Organisation {
+                old: self.organisation,
+                new: newer.organisation,
+            });
+        }
+
+        if self.publication_state != newer.publication_state {
+            changes.push(FieldChange::PublicationState {
+                old: self.publication_state,
+                new: newer.publication_state,
+            });
+        }
+
+        changes
+    }
+}
+
+impl Locations {
+    /// Computes difference between `self` and `newer` snapshot of the database.
+    pub fn diff(&self, newer: &Locations) -> LocationsDiff {
+        let mut diff = LocationsDiff::default();
+
+        let is_present = |locations: &Locations, id: &Uuid| {
+            locations
+                .locations
+                .get(id)
+                .filter(|location| !location.is_deleted())
+                .cloned()
+        };
+        for new_location in newer.locations.values() {
+            if new_location.is_deleted() {
+                continue;
+            }
+            match is_present(self, &new_location.get_id()) {
+                None => diff.added.push(new_location.clone()),
+                Some(old_location) => {
+                    let changes = old_location.diff(new_location);
+                    if !changes.is_empty() {
+                        diff.changed.push(LocationDiff {
+                            id: new_location.get_id(),
+                            name: new_location.name.to_string(),
+                            changes,
+                        });
+                    }
+                }
+            }
+        }
+        diff.removed = self
+            .locations
+            .values()
+            .filter(|location| !location.is_deleted())
+            .filter(|location| is_present(newer, &location.get_id()).is_none())
+            .cloned()
+            .collect();
+
+        for change in diff
+            .changed
+            .iter()
+            .flat_map(|location| location.changes.iter())
+        {
+            if let FieldChange::Organisation { old, new } = change {
+                for id in old.iter().chain(new.iter()) {
+                    let organisation = newer
+                        .organisations
+                        .get(id)
+                        .or_else(|| self.organisations.get(id));
+                    if let Some(organisation) = organisation {
+                        diff.organisation_names
+                            .insert(*id, organisation.name.to_string());
+                    }
+                }
+            }
+        }
+
+        diff.added
+            .sort_by(|l1, l2| l1.name.default_text().cmp(l2.name.default_text()));
+        diff.removed
+            .sort_by(|l1, l2| l1.name.default_text().cmp(l2.name.default_text()));
+        diff.changed.sort_by(|l1, l2| l1.name.cmp(&l2.name));
+        diff
+    }
+}
+
+fn time_span_to_string(time_span: &Option<TimeSpan>) -> String {
+    match time_span {
+        Some(time_span) => format!(
+            "{}-{}",
+            time_span.from.to_time_string(),
+            time_span.to.to_time_string()
+        ),
+        None => t!("diff-closed").to_string(),
+    }
+}
+
+fn coordinates_to_string(coordinates: &Option<GeoPoint>) -> String {
+    match coordinates {
+        Some(coordinates) => coordinates.to_string(),
+        None => t!("diff-no-coordinates").to_string(),
+    }
+}
+
+/// Organisation name in quotes, its ID when the name is not known.
+fn organisation_to_string(id: &Option<Uuid>, names: &BTreeMap<Uuid, String>) -> String {
+    match id {
+        Some(id) => match names.get(id) {
+            Some(name) => format!("\"{name}\""),
+            None => id.to_string(),
+        },
+        None => t!("diff-no-organisation").to_string(),
+    }
+}
+
+impl fmt::Display for FieldChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FieldChange::Name { locale, old, new } => {
+                write!(f, "{} ({locale}): \"{old}\" -> \"{new}\"", t!("diff-name"))
+            }
+            FieldChange::Address { old, new } => {
+                write!(f, "{}: \"{old}\" -> \"{new}\"", t!("diff-address"))
+            }
+            FieldChange::Description { locale, old, new } => {
+                write!(
+                    f,
+                    "{} ({locale}): \"{old}\" -> \"{new}\"",
+                    t!("diff-description")
+                )
+            }
+            FieldChange::TagAdded(tag) => {
+                write!(f, "{}: +{}", t!("diff-tags"), tag.human_readable())
+            }
+            FieldChange::TagRemoved(tag) => {
+                write!(f, "{}: -{}", t!("diff-tags"), tag.human_readable())
+            }
+            FieldChange::ContactAdded(contact) => write!(
+                f,
+                "{}: +{} {}",
+                t!("diff-contact"),
+                contact.contact_type.to_string(),
+                contact.value
+            ),
+            FieldChange::ContactRemoved(contact) => write!(
+                f,
+                "{}: -{} {}",
+                t!("diff-contact"),
+                contact.contact_type.to_string(),
+                contact.value
+            ),
+            FieldChange::Coordinates { old, new } => write!(
+                f,
+                "{}: {} -> {}",
+                t!("diff-coordinates"),
+                coordinates_to_string(old),
+                coordinates_to_string(new)
+            ),
+            FieldChange::OpenedHours { day, old, new } => write!(
+                f,
+                "{}: {} -> {}",
+                day.to_display_name(),
+                time_span_to_string(old),
+                time_span_to_string(new)
+            ),
+            FieldChange::Accessibility { feature, old, new } => {
+                write!(f, "{feature}: {old} -> {new}")
+            }
+            FieldChange::Organisation { old, new } => write!(
+                f,
+                "{}: {} -> {}",
+                t!("diff-organisation"),
+                organisation_to_string(old, &BTreeMap::new()),
+                organisation_to_string(new, &BTreeMap::new())
+            ),
+            FieldChange::PublicationState { old, new } => {
+                write!(f, "{}: {old} -> {new}", t!("diff-publication-state"))
+            }
+            FieldChange::ServiceTerms { old, new } => {
+                write!(f, "{}: \"{old}\" -> \"{new}\"", t!("diff-service-terms"))
+            }
+        }
+    }
+}
+
+/// Renders diff as a human-readable changelog, one entry per line.
+impl fmt::Display for LocationsDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for location in self.added.iter() {
+            writeln!(f, "+ {}: {}", t!("diff-added"), location.name)?;
+        }
+        for location in self.removed.iter() {
+            writeln!(f, "- {}: {}", t!("diff-removed"), location.name)?;
+        }
+        for location in self.changed.iter() {
+            writeln!(f, "* {}: {}", t!("diff-changed"), location.name)?;
+            for change in location.changes.iter() {
+                match change {
+                    FieldChange::Organisation { old, new } => writeln!(
+                        f,
+                        "    {}: {} -> {}",
+                        t!("diff-organisation"),
+                        organisation_to_string(old, &self.organisation_names),
+                        organisation_to_string(new, &self.organisation_names)
+                    )?,
+                    change => writeln!(f, "    {change}")?,
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contact::ContactType;
+    use crate::organisations::Organisation;
+    use crate::time::TimePoint;
+
+    #[test]
+    fn diff_lists_field_changes() {
+        let old = Location::default();
+        let mut new = old.clone();
+        new.name = "New name".into();
+        new.tags.define_tag("LGBT");
+        new.contact_methods.add_new_contact_method(Contact {
+            contact_type: ContactType::PhoneNumber,
+            value: "123".to_string(),
+        });
+        let time_span = TimeSpan {
+            from: TimePoint { hour: 9, minute: 0 },
+            to: TimePoint {
+                hour: 17,
+                minute: 0,
+            },
+        };
+        new.opened_hours
+            .set_day_time_span(Day::Monday, time_span.clone());
+
+        assert_eq!(
+            old.diff(&new),
+            vec![
+                FieldChange::Name {
+                    locale: "pl".to_string(),
+                    old: "".to_string(),
+                    new: "New name".to_string()
+                },
+                FieldChange::TagAdded(Tag::new("LGBT".to_string())),
+                FieldChange::ContactAdded(new.contact_methods.all_contact_methods()[0].clone()),
+                FieldChange::OpenedHours {
+                    day: Day::Monday,
+                    old: None,
+                    new: Some(time_span)
+                },
+            ]
+        );
+        assert!(new.diff(&new).is_empty());
+    }
+
+    #[test]
+    fn diff_between_databases_renders_changelog() {
+        let mut old = Locations::new();
+        let kept = old.push_new(|loc| loc.name = "Kept".into());
+        let removed = old.push_new(|loc| loc.name = "Removed".into());
+
+        let mut new = old.clone();
+        new.remove(removed).unwrap();
+        let mut renamed = kept.clone();
+        renamed.name = "Renamed".into();
+        new.push_update(renamed).unwrap();
+        new.push_new(|loc| loc.name = "Added".into());
+
+        let diff = old.diff(&new);
+        assert_eq!(diff.added.len(), 1);
+        assert_eq!(diff.removed.len(), 1);
+        assert_eq!(diff.changed.len(), 1);
+
+        let changelog = diff.to_string();
+        assert_eq!(changelog.lines().count(), 4);
+        assert!(changelog.contains("Added"));
+        assert!(changelog.contains("\"Kept\" -> \"Renamed\""));
+    }
+
+    #[test]
+    fn trashed_locations_are_listed_as_removed() {
+        let mut old = Locations::new();
+        let location = old.push_new(|loc| loc.name = "Trashed".into());
+
+        let mut new = old.clone();
+        new.move_to_trash(&location).unwrap();
+        let diff = old.diff(&new);
+        assert_eq!(diff.removed, vec![location]);
+        assert!(diff.changed.is_empty());
+
+        let diff = new.diff(&old);
+        assert_eq!(diff.added.len(), 1);
+        assert!(diff.removed.is_empty());
+    }
+
+    #[test]
+    fn changelog_shows_old_and_new_values() {
+        let mut old = Locations::new();
+        let mut organisation = Organisation::default();
+        organisation.name = "Fundacja".into();
+        old.push_organisation(organisation.clone());
+        let location = old.push_new(|loc| loc.description = "Old".into());
+
+        let mut new = old.clone();
+        let mut changed = location.clone();
+        changed.description = "New".into();
+        changed.organisation = Some(organisation.get_id());
+        changed.transition_to(PublicationState::Archived).unwrap();
+        new.push_update(changed).unwrap();
+
+        let changelog = old.diff(&new).to_string();
+        assert!(changelog.contains("\"Old\" -> \"New\""));
+        assert!(changelog.contains("\"Fundacja\""));
+        assert!(changelog.contains(&PublicationState::Archived.to_string()));
+    }
+}
@@ -0,0 +1,371 @@
+/*
+Copyright (C) 2025 Mateusz Mazur (Mazurel) <mateusz.mazur@e.email>
+
+This program is free software; you can redistribute it and/or
+modify it under the terms of the GNU General Public License
+as published by the Free Software Foundation; either version 2
+of the License, or (at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program; if not, see
+<https://www.gnu.org/licenses/>.
+*/
+
+//! Detection of locations describing the same place, and merging them.
+
+use crate::{
+    address::Address,
+    contact::{Contact, ContactType},
+    error::Result,
+    fuzzy::edit_distance,
+    journal::JournaledLocations,
+    locations::{Location, Locations},
+    search::{fold_diacritics, terms},
+};
+use std::collections::HashSet;
+use uuid::Uuid;
+
+/// Candidates scoring below this value are not reported.
+pub const DUPLICATE_THRESHOLD: f32 = 0.6;
+/// Similarities below this value are treated as noise.
+const MIN_SIMILARITY: f32 = 0.5;
+
+const NAME_WEIGHT: f32 = 0.7;
+const ADDRESS_WEIGHT: f32 = 0.5;
+const CONTACT_WEIGHT: f32 = 0.8;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DuplicateReason {
+    /// Similarity of the names, between 0 and 1.
+    SimilarName(f32),
+    SimilarAddress(f32),
+    SharedContact(Contact),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DuplicateCandidate {
+    pub first: Uuid,
+    pub second: Uuid,
+    /// Likelihood that both locations describe the same place, between 0 and 1.
+    pub score: f32,
+    pub reasons: Vec<DuplicateReason>,
+}
+
+fn jaccard(a: &HashSet<String>, b: &HashSet<String>) -> f32 {
+    let union = a.union(b).count();
+    match union {
+        0 => 0.0,
+        union => a.intersection(b).count() as f32 / union as f32,
+    }
+}
+
+/// Best of terms overlap and normalized edit distance, so that both
+/// reordered words and typos are recognized.
+fn text_similarity(a: &str, b: &str) -> f32 {
+    let (folded_a, folded_b) = (fold_diacritics(a.trim()), fold_diacritics(b.trim()));
+    let longer = folded_a.chars().count().max(folded_b.chars().count());
+    if longer == 0 {
+        return 0.0;
+    }
+    let by_edits = 1.0 - edit_distance(&folded_a, &folded_b) as f32 / longer as f32;
+    let terms_of = |text: &str| terms(text).into_iter().collect::<HashSet<_>>();
+    jaccard(&terms_of(a), &terms_of(b)).max(by_edits)
+}
+
+fn address_similarity(a: &Location, b: &Location) -> f32 {
+    let (a, b) = (&a.address, &b.address);
+    let is_structured =
+        |address: &Address| !address.postal_code.is_empty() && !address.building.is_empty();
+    if is_structured(a) && is_structured(b) {
+        let same_building = a.postal_code == b.postal_code
+            && fold_diacritics(&a.building) == fold_diacritics(&b.building);
+        return match same_building {
+            true => text_similarity(&a.street, &b.street),
+            false => 0.0,
+        };
+    }
+    text_similarity(&a.to_string(), &b.to_string())
+}
+
+/// Contact value in a form that does not depend on formatting,
+/// e.g. "+48 58 123-45-67" and "581234567" are the same phone number.
+fn normalized_contact(contact: &Contact) -> Option<(ContactType, String)> {
+    let value = contact.value.trim();
+    let normalized = match contact.contact_type {
+        ContactType::PhoneNumber => {
+            let digits: String = value.chars().filter(char::is_ascii_digit).collect();
+            match digits.strip_prefix("48") {
+                Some(national) if digits.len() == 11 => national.to_string(),
+                _ => digits,
+            }
+        }
+        ContactType::Email => value.to_lowercase(),
+        // Many unrelated places share websites of their umbrella organisations
+        ContactType::WebAddress => return None,
+    };
+    (!normalized.is_empty()).then_some((contact.contact_type, normalized))
+}
+
+fn shared_contacts(a: &Location, b: &Location) -> Vec<Contact> {
+    let contacts_of_b: HashSet<_> = b
+        .contact_methods
+        .all_contact_methods()
+        .iter()
+        .filter_map(normalized_contact)
+        .collect();
+    a.contact_methods
+        .all_contact_methods()
+        .iter()
+        .filter(|contact| {
+            normalized_contact(contact)
+                .is_some_and(|normalized| contacts_of_b.contains(&normalized))
+        })
+        .cloned()
+        .collect()
+}
+
+impl DuplicateCandidate {
+    /// Compares two locations, returns `None` if they score below `DUPLICATE_THRESHOLD`.
+    pub fn new(first: &Location, second: &Location) -> Option<Self> {
+        let mut reasons = vec![];
+        // Evidence is combined as in noisy-OR, each reason alone may be enough
+        let mut not_duplicate = 1.0;
+
+        let name_similarity =
+            text_similarity(first.name.default_text(), second.name.default_text());
+        if name_similarity >= MIN_SIMILARITY {
+            not_duplicate *= 1.0 - NAME_WEIGHT * name_similarity;
+            reasons.push(DuplicateReason::SimilarName(name_similarity));
+        }
+        let address_similarity = address_similarity(first, second);
+        if address_similarity >= MIN_SIMILARITY {
+            not_duplicate *= 1.0 - ADDRESS_WEIGHT * address_similarity;
+            reasons.push(DuplicateReason::SimilarAddress(address_similarity));
+        }
+        for contact in shared_contacts(first, second) {
+            not_duplicate *= 1.0 - CONTACT_WEIGHT;
+            reasons.push(DuplicateReason::SharedContact(contact));
+        }
+
+        let score = 1.0 - not_duplicate;
+        (score >= DUPLICATE_THRESHOLD).then_some(DuplicateCandidate {
+            first: first.get_id(),
+            second: second.get_id(),
+            score,
+            reasons,
+        })
+    }
+}
+
+impl Locations {
+    /// Pairs of locations that possibly describe the same place, most likely first.
+    pub fn find_duplicates(&self) -> Vec<DuplicateCandidate> {
+        let mut locations: Vec<&Location> = self
+            .locations
+            .values()
+            .filter(|location| !location.is_deleted())
+            .collect();
+        locations.sort_by_key(|location| location.get_id());
+
+        let mut candidates = vec![];
+        for (idx, first) in locations.iter().enumerate() {
+            for second in locations[idx + 1..].iter() {
+                candidates.extend(DuplicateCandidate::new(first, second));
+            }
+        }
+        candidates.sort_by(|c1, c2| c2.score.total_cmp(&c1.score));
+        candidates
+    }
+}
+
+/// Which of the duplicates a field is taken from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MergeSide {
+    #[default]
+    Kept,
+    Removed,
+}
+
+/// Choice of the field values of the merged location.
+/// Tags and contact methods of both locations are always joined.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct FieldSelection {
+    pub name: MergeSide,
+    pub description: MergeSide,
+    pub address: MergeSide,
+    pub coordinates: MergeSide,
+    pub opened_hours: MergeSide,
+    pub accessibility: MergeSide,
+    pub service_terms: MergeSide,
+    pub organisation: MergeSide,
+}
+
+/// Location with ID of `kept` and fields chosen by `selection`.
+pub fn merge_duplicates(
+    kept: &Location,
+    removed: &Location,
+    selection: &FieldSelection,
+) -> Location {
+    let pick = |side: MergeSide| match side {
+        MergeSide::Kept => kept,
+        MergeSide::Removed => removed,
+    };
+    let mut merged = kept.clone();
+    merged.name = pick(selection.name).name.clone();
+    merged.description = pick(selection.description).description.clone();
+    merged.address = pick(selection.address).address.clone();
+    merged.coordinates = pick(selection.coordinates).coordinates;
+    merged.opened_hours = pick(selection.opened_hours).opened_hours.clone();
+    merged.accessibility = pick(selection.accessibility).accessibility.clone();
+    merged.service_terms = pick(selection.service_terms).service_terms.clone();
+    merged.organisation = pick(selection.organisation).organisation;
+
+    for tag in removed.tags.get_all_tags() {
+        merged.tags.define_tag(&tag.name);
+    }
+    let known_contacts: HashSet<_> = kept
+        .contact_methods
+        .all_contact_methods()
+        .iter()
+        .map(|contact| {
+            normalized_contact(contact).unwrap_or((contact.contact_type, contact.value.clone()))
+        })
+        .collect();
+    for contact in removed.contact_methods.all_contact_methods() {
+        let normalized =
+            normalized_contact(contact).unwrap_or((contact.contact_type, contact.value.clone()));
+        if !known_contacts.contains(&normalized) {
+            merged
+                .contact_methods
+                .add_new_contact_method(contact.clone());
+        }
+    }
+    merged
+}
+
+impl JournaledLocations<'_> {
+    /// Replaces `kept` with the merge of both duplicates and moves the other one
+    /// to the trash, as a single operation.
+    pub fn merge_duplicates(
+        &mut self,
+        kept: &Location,
+        removed: &Location,
+        selection: &FieldSelection,
+    ) -> Result<Location> {
+        let merged = merge_duplicates(kept, removed, selection);
+        self.batch(|journaled| {
+            let merged = journaled.push_update(merged)?;
+            journaled.move_to_trash(removed)?;
+            Ok(merged)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::accessibility::{AccessibilityFeature, Availability};
+    use crate::journal::Journal;
+    use crate::service_terms::CostKind;
+
+    fn location(name: &str, address: &str, phone: &str) -> Location {
+        let mut location = Location::default();
+        location.name = name.into();
+        location.address = Address::from_free_form(address);
+        if !phone.is_empty() {
+            location.contact_methods.add_new_contact_method(Contact {
+                contact_type: ContactType::PhoneNumber,
+                value: phone.to_string(),
+            });
+        }
+        location
+    }
+
+    #[test]
+    fn duplicates_are_scored() {
+        let original = location(
+            "Ośrodek Interwencji Kryzysowej",
+            "ul. Długa 5, 80-001 Gdańsk",
+            "+48 58 123 45 67",
+        );
+        let typo = location(
+            "Osrodek Interwencji Kryzysowj",
+            "ul. Dluga 5, 80-001 Gdańsk",
+            "",
+        );
+        let same_phone = location("OIK", "", "58 123-45-67");
+        let unrelated = location("Schronisko", "ul. Morska 10, 81-225 Gdynia", "600 000 000");
+
+        let typo_candidate = DuplicateCandidate::new(&original, &typo).unwrap();
+        assert!(typo_candidate.score > 0.8, "{typo_candidate:?}");
+        let phone_candidate = DuplicateCandidate::new(&original, &same_phone).unwrap();
+        assert_eq!(
+            phone_candidate.reasons,
+            vec![DuplicateReason::SharedContact(
+                original.contact_methods.all_contact_methods()[0].clone()
+            )]
+        );
+        assert_eq!(DuplicateCandidate::new(&original, &unrelated), None);
+
+        let mut locations = Locations::new();
+        for location in [&original, &typo, &same_phone, &unrelated] {
+            locations.force_update(location.clone());
+        }
+        let candidates = locations.find_duplicates();
+        assert_eq!(candidates.len(), 2);
+        assert!(candidates[0].score >= candidates[1].score);
+    }
+
+    #[test]
+    fn merge_keeps_one_id_and_is_undone_at_once() {
+        let mut kept = location("OIK", "ul. Długa 5, 80-001 Gdańsk", "58 123 45 67");
+        kept.tags.define_tag("Gdańsk");
+        let mut removed = location("Ośrodek Interwencji Kryzysowej", "", "+48 58 123 45 67");
+        removed.tags.define_tag("NGO");
+        removed
+            .accessibility
+            .set(AccessibilityFeature::StepFreeAccess, Availability::Yes);
+        removed.service_terms.cost = CostKind::Free;
+        removed.organisation = Some(Uuid::new_v4());
+        removed.contact_methods.add_new_contact_method(Contact {
+            contact_type: ContactType::Email,
+            value: "oik@example.org".to_string(),
+        });
+
+        let mut locations = Locations::new();
+        let mut journal = Journal::new();
+        let mut journaled = JournaledLocations::new(&mut locations, &mut journal);
+        let kept = journaled.force_update(kept);
+        let removed = journaled.force_update(removed);
+
+        let selection = FieldSelection {
+            name: MergeSide::Removed,
+            accessibility: MergeSide::Removed,
+            service_terms: MergeSide::Removed,
+            organisation: MergeSide::Removed,
+            ..Default::default()
+        };
+        let merged = journaled
+            .merge_duplicates(&kept, &removed, &selection)
+            .unwrap();
+        assert_eq!(merged.get_id(), kept.get_id());
+        assert_eq!(merged.name, removed.name);
+        assert_eq!(merged.address, kept.address);
+        assert_eq!(merged.accessibility, removed.accessibility);
+        assert_ne!(merged.accessibility, kept.accessibility);
+        assert_eq!(merged.service_terms.cost, CostKind::Free);
+        assert_eq!(merged.organisation, removed.organisation);
+        assert_eq!(merged.tags.get_all_tags().len(), 2);
+        assert_eq!(merged.contact_methods.len(), 2);
+        assert!(journaled.locations[&removed.get_id()].is_deleted());
+
+        journaled.undo();
+        assert!(!journaled.locations[&removed.get_id()].is_deleted());
+        assert_eq!(journaled.locations[&kept.get_id()].name, kept.name);
+    }
+}
@@ -0,0 +1,120 @@
+/*
+Copyright (C) 2025 Mateusz Mazur (Mazurel) <mateusz.mazur@e.email>
+
+This program is free software; you can redistribute it and/or
+modify it under the terms of the GNU General Public License
+as published by the Free Software Foundation; either version 2
+of the License, or (at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program; if not, see
+<https://www.gnu.org/licenses/>.
+*/
+
+use crate::{
+    locations::Location, migrations::SchemaVersion, publication::PublicationState,
+    validation::ValidationIssue,
+};
+use std::fmt;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// Data could not be (de)serialized, the message describes details.
+    CorruptData(String),
+    /// Data was written with a schema version this library cannot read.
+    UnsupportedVersion {
+        version: SchemaVersion,
+        supported_version: SchemaVersion,
+    },
+    /// Location with the given ID is not in the database.
+    MissingLocation(Uuid),
+    /// Location is stored under a key that differs from its own ID.
+    IdMismatch { key: Uuid, id: Uuid },
+    /// Location was modified in the database after `stale` was fetched.
+    Conflict {
+        stale: Box<Location>,
+        current: Box<Location>,
+    },
+    /// Only locations in the trash can be purged.
+    NotInTrash(Uuid),
+    /// Publication state cannot be changed this way, see `PublicationState::allowed_transitions`.
+    InvalidTransition {
+        from: PublicationState,
+        to: PublicationState,
+    },
+    /// Location cannot be published because of these validation errors.
+    ValidationFailed(Vec<ValidationIssue>),
+    /// Organisation with the given ID is not in the database.
+    MissingOrganisation(Uuid),
+    /// Organisation with the given name still has branches, so it cannot be removed.
+    OrganisationHasBranches(String),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::CorruptData(details) => {
+                write!(f, "{}: {details}", t!("error-corrupt-data"))
+            }
+            Error::UnsupportedVersion {
+                version,
+                supported_version,
+            } => write!(
+                f,
+                "{}: {version} > {supported_version}",
+                t!("error-unsupported-version")
+            ),
+            Error::MissingLocation(id) => {
+                write!(f, "{}: {id}", t!("error-missing-location"))
+            }
+            Error::IdMismatch { key, id } => {
+                write!(f, "{}: {key} != {id}", t!("error-id-mismatch"))
+            }
+            Error::Conflict { current, .. } => {
+                write!(f, "{}: {}", t!("error-location-conflict"), current.name)
+            }
+            Error::NotInTrash(id) => {
+                write!(f, "{}: {id}", t!("error-not-in-trash"))
+            }
+            Error::InvalidTransition { from, to } => {
+                write!(f, "{}: {from} → {to}", t!("error-invalid-transition"))
+            }
+            Error::ValidationFailed(issues) => {
+                let issues = issues
+                    .iter()
+                    .map(ValidationIssue::to_string)
+                    .collect::<Vec<_>>()
+                    .join("; ");
+                write!(f, "{}: {issues}", t!("error-validation-failed"))
+            }
+            Error::MissingOrganisation(id) => {
+                write!(f, "{}: {id}", t!("error-missing-organisation"))
+            }
+            Error::OrganisationHasBranches(name) => {
+                write!(f, "{}: {name}", t!("error-organisation-has-branches"))
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<bson::de::Error> for Error {
+    fn from(value: bson::de::Error) -> Self {
+        Error::CorruptData(value.to_string())
+    }
+}
+
+impl From<bson::ser::Error> for Error {
+    fn from(value: bson::ser::Error) -> Self {
+        Error::CorruptData(value.to_string())
+    }
+}
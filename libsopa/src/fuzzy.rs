@@ -0,0 +1,130 @@
+/*
+Copyright (C) 2025 Mateusz Mazur (Mazurel) <mateusz.mazur@e.email>
+
+This program is free software; you can redistribute it and/or
+modify it under the terms of the GNU General Public License
+as published by the Free Software Foundation; either version 2
+of the License, or (at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program; if not, see
+<https://www.gnu.org/licenses/>.
+*/
+
+use crate::{
+    search::terms,
+    tags::{Tag, Tags},
+};
+
+/// Levenshtein distance between `a` and `b`, counted in characters.
+///
+/// ```rust
+/// assert_eq!(libsopa::fuzzy::edit_distance("alkohol", "alcohol"), 1);
+/// ```
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous_row: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current_row = vec![i + 1; b.len() + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution_cost = usize::from(a_char != *b_char);
+            current_row[j + 1] = (previous_row[j] + substitution_cost)
+                .min(previous_row[j + 1] + 1)
+                .min(current_row[j] + 1);
+        }
+        previous_row = current_row;
+    }
+
+    previous_row[b.len()]
+}
+
+/// Amount of typos tolerated in a word, short words must match exactly.
+pub fn max_typos(word: &str) -> usize {
+    match word.chars().count() {
+        0..=3 => 0,
+        4..=6 => 1,
+        _ => 2,
+    }
+}
+
+/// Whether `candidate` is close enough to the typed `word`.
+/// Both should be already normalized with `search::terms`.
+pub fn is_fuzzy_match(word: &str, candidate: &str) -> bool {
+    let tolerance = max_typos(word);
+    word.chars().count().abs_diff(candidate.chars().count()) <= tolerance
+        && edit_distance(word, candidate) <= tolerance
+}
+
+/// Finds the closest of the `candidates` within the typo threshold.
+/// Ties are resolved by the order of `candidates`.
+pub fn closest_match<'a, It>(word: &str, candidates: It) -> Option<&'a str>
+where
+    It: IntoIterator<Item = &'a str>,
+{
+    candidates
+        .into_iter()
+        .filter(|candidate| is_fuzzy_match(word, candidate))
+        .min_by_key(|candidate| edit_distance(word, candidate))
+}
+
+impl Tag {
+    /// Whether any word of the `query` approximately matches
+    /// any word of the tag name, in any language.
+    pub fn fuzzy_matches(&self, query: &str) -> bool {
+        let tag_terms: Vec<String> = self
+            .human_readable_in_every_locale()
+            .iter()
+            .flat_map(|name| terms(name))
+            .collect();
+        terms(query).iter().any(|query_term| {
+            tag_terms
+                .iter()
+                .any(|tag_term| is_fuzzy_match(query_term, tag_term))
+        })
+    }
+}
+
+impl Tags {
+    /// Tags approximately matching the `query`, see `Tag::fuzzy_matches`.
+    pub fn fuzzy_find(&self, query: &str) -> Vec<&Tag> {
+        self.get_all_tags_in_order()
+            .into_iter()
+            .filter(|tag| tag.fuzzy_matches(query))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tags::get_all_supported_tags;
+
+    #[test]
+    fn typo_threshold_scales_with_word_length() {
+        assert!(!is_fuzzy_match("ngo", "nfz"));
+        assert!(is_fuzzy_match("alkohol", "alcohol"));
+        assert!(is_fuzzy_match("trojmiasto", "trujmiast"));
+        assert!(!is_fuzzy_match("prawo", "terap"));
+        assert_eq!(
+            closest_match("gdnsk", ["gdynia", "gdansk", "sopot"]),
+            Some("gdansk")
+        );
+    }
+
+    #[test]
+    fn tags_are_found_in_every_language() {
+        let found: Vec<String> = get_all_supported_tags()
+            .fuzzy_find("alkohol")
+            .into_iter()
+            .map(|tag| tag.name.clone())
+            .collect();
+        assert_eq!(found, vec!["alcohol abuse".to_string()]);
+        assert!(Tag::new("Gdańsk".to_string()).fuzzy_matches("Gdansk"));
+    }
+}
@@ -0,0 +1,217 @@
+/*
+Copyright (C) 2025 Mateusz Mazur (Mazurel) <mateusz.mazur@e.email>
+
+This program is free software; you can redistribute it and/or
+modify it under the terms of the GNU General Public License
+as published by the Free Software Foundation; either version 2
+of the License, or (at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program; if not, see
+<https://www.gnu.org/licenses/>.
+*/
+
+//! Small offline gazetteer of Pomorskie, used to infer GeoLocation tags from addresses.
+
+use crate::{
+    address::Address,
+    geo::GeoPoint,
+    locations::Location,
+    search::fold_diacritics,
+    tags::{get_all_supported_tags_of_group, Tag, TagGroup, Tags},
+};
+
+/// Locality and the most specific GeoLocation tag it belongs to.
+pub struct Locality {
+    pub name: &'static str,
+    pub tag_name: &'static str,
+    pub center: GeoPoint,
+}
+
+/// Postal codes starting with `prefix` belong to the `tag_name` area.
+pub struct PostalArea {
+    pub prefix: &'static str,
+    pub tag_name: &'static str,
+}
+
+macro_rules! localities {
+    ($($name:literal => $tag_name:literal at ($latitude:literal, $longitude:literal)),* $(,)?) => {
+        &[$(Locality {
+            name: $name,
+            tag_name: $tag_name,
+            center: match GeoPoint::new($latitude, $longitude) {
+                Some(center) => center,
+                None => panic!("Center of the locality is out of range"),
+            },
+        }),*]
+    };
+}
+
+macro_rules! postal_areas {
+    ($($prefix:literal => $tag_name:literal),* $(,)?) => {
+        &[$(PostalArea { prefix: $prefix, tag_name: $tag_name }),*]
+    };
+}
+
+pub const LOCALITIES: &[Locality] = localities!(
+    "Gdańsk" => "Gdańsk" at (54.3520, 18.6466),
+    "Gdynia" => "Gdynia" at (54.5189, 18.5305),
+    "Sopot" => "Sopot" at (54.4416, 18.5601),
+    "Bytów" => "Pomorskie" at (54.1706, 17.4919),
+    "Chojnice" => "Pomorskie" at (53.6955, 17.5570),
+    "Człuchów" => "Pomorskie" at (53.6647, 17.3620),
+    "Hel" => "Pomorskie" at (54.6081, 18.8009),
+    "Jastarnia" => "Pomorskie" at (54.6966, 18.6788),
+    "Kartuzy" => "Pomorskie" at (54.3340, 18.1970),
+    "Kolbudy" => "Pomorskie" at (54.2720, 18.4650),
+    "Kościerzyna" => "Pomorskie" at (54.1222, 17.9775),
+    "Kwidzyn" => "Pomorskie" at (53.7294, 18.9233),
+    "Lębork" => "Pomorskie" at (54.5392, 17.7501),
+    "Malbork" => "Pomorskie" at (54.0359, 19.0266),
+    "Nowy Dwór Gdański" => "Pomorskie" at (54.2128, 19.1178),
+    "Pruszcz Gdański" => "Pomorskie" at (54.2620, 18.6361),
+    "Puck" => "Pomorskie" at (54.7171, 18.4080),
+    "Reda" => "Pomorskie" at (54.6050, 18.3490),
+    "Rumia" => "Pomorskie" at (54.5709, 18.3878),
+    "Słupsk" => "Pomorskie" at (54.4641, 17.0285),
+    "Starogard Gdański" => "Pomorskie" at (53.9646, 18.5286),
+    "Sztum" => "Pomorskie" at (53.9206, 19.0306),
+    "Tczew" => "Pomorskie" at (54.0924, 18.7779),
+    "Ustka" => "Pomorskie" at (54.5805, 16.8619),
+    "Wejherowo" => "Pomorskie" at (54.6059, 18.2353),
+    "Władysławowo" => "Pomorskie" at (54.7909, 18.4014),
+    "Żukowo" => "Pomorskie" at (54.3430, 18.3640),
+);
+
+/// Longer prefixes take precedence, e.g. "81-7" (Sopot) over "81-" (Gdynia).
+pub const POSTAL_AREAS: &[PostalArea] = postal_areas!(
+    "80-" => "Gdańsk",
+    "81-" => "Gdynia",
+    "81-7" => "Sopot",
+    "81-8" => "Sopot",
+    "76-2" => "Pomorskie",
+    "77-1" => "Pomorskie",
+    "77-3" => "Pomorskie",
+    "82-1" => "Pomorskie",
+    "82-2" => "Pomorskie",
+    "82-4" => "Pomorskie",
+    "82-5" => "Pomorskie",
+    "83-" => "Pomorskie",
+    "84-" => "Pomorskie",
+    "89-6" => "Pomorskie",
+);
+
+/// Region directly containing the area of the tag, e.g. Gdańsk lies in Trojmiasto.
+pub fn parent_region(tag_name: &str) -> Option<&'static str> {
+    match tag_name {
+        "Gdańsk" | "Gdynia" | "Sopot" => Some("Trojmiasto"),
+        "Trojmiasto" => Some("Pomorskie"),
+        _ => None,
+    }
+}
+
+/// Cities the user can pick instead of sharing their position,
+/// those having their own GeoLocation tag.
+pub fn known_cities() -> impl Iterator<Item = &'static Locality> {
+    LOCALITIES
+        .iter()
+        .filter(|locality| locality.name == locality.tag_name)
+}
+
+pub fn find_locality(city: &str) -> Option<&'static Locality> {
+    let city = fold_diacritics(city.trim());
+    LOCALITIES
+        .iter()
+        .find(|locality| fold_diacritics(locality.name) == city)
+}
+
+fn find_postal_area(postal_code: &str) -> Option<&'static PostalArea> {
+    POSTAL_AREAS
+        .iter()
+        .filter(|area| postal_code.starts_with(area.prefix))
+        .max_by_key(|area| area.prefix.len())
+}
+
+/// GeoLocation tags of the `address`, together with all of the regions they lie in.
+///
+/// City name is trusted more than the postal code, since the latter is often mistyped.
+pub fn infer_geo_tags(address: &Address) -> Tags {
+    let mut tags = Tags::new();
+    let most_specific = find_locality(&address.city)
+        .map(|locality| locality.tag_name)
+        .or_else(|| find_postal_area(&address.postal_code).map(|area| area.tag_name))
+        .or_else(|| {
+            (fold_diacritics(address.voivodeship.trim()) == "pomorskie").then_some("Pomorskie")
+        });
+
+    let mut tag_name = most_specific;
+    while let Some(name) = tag_name {
+        tags.define_tag(name);
+        tag_name = parent_region(name);
+    }
+    tags
+}
+
+impl Location {
+    /// GeoLocation tags inferred from the address, that the location does not have yet.
+    pub fn suggested_geo_tags(&self) -> Vec<Tag> {
+        let supported = get_all_supported_tags_of_group(&TagGroup::GeoLocation);
+        infer_geo_tags(&self.address)
+            .get_all_tags_in_order()
+            .into_iter()
+            .filter(|tag| supported.has_tag(tag) && !self.tags.has_tag(tag))
+            .cloned()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inferred(text: &str) -> Tags {
+        infer_geo_tags(&Address::from_free_form(text))
+    }
+
+    #[test]
+    fn geo_tags_are_inferred_with_implied_regions() {
+        assert_eq!(
+            inferred("ul. Bohaterów Monte Cassino 5, 81-767 Sopot"),
+            Tags::new_tags(["Sopot", "Trojmiasto", "Pomorskie"])
+        );
+        // Postal code is used when the city is not known
+        assert_eq!(
+            inferred("ul. Morska 10, 81-225 Gdynia-Chylonia"),
+            Tags::new_tags(["Gdynia", "Trojmiasto", "Pomorskie"])
+        );
+        assert_eq!(
+            inferred("ul. Kościuszki 2, 83-110 tczew"),
+            Tags::new_tags(["Pomorskie"])
+        );
+        assert_eq!(
+            inferred("ul. Marszałkowska 1, 00-001 Warszawa"),
+            Tags::new()
+        );
+        assert_eq!(inferred(""), Tags::new());
+    }
+
+    #[test]
+    fn only_missing_tags_are_suggested() {
+        let mut location = Location::default();
+        location.address = Address::from_free_form("ul. Długa 5, 80-001 Gdańsk");
+        location.tags.define_tag("Pomorskie");
+
+        assert_eq!(
+            location.suggested_geo_tags(),
+            vec![
+                Tag::new("Gdańsk".to_string()),
+                Tag::new("Trojmiasto".to_string())
+            ]
+        );
+    }
+}
@@ -0,0 +1,236 @@
+/*
+Copyright (C) 2025 Mateusz Mazur (Mazurel) <mateusz.mazur@e.email>
+
+This program is free software; you can redistribute it and/or
+modify it under the terms of the GNU General Public License
+as published by the Free Software Foundation; either version 2
+of the License, or (at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program; if not, see
+<https://www.gnu.org/licenses/>.
+*/
+
+use crate::{
+    locations::{Location, Locations},
+    ranking::RankedLocation,
+};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+const EARTH_RADIUS_KM: f64 = 6371.0;
+
+/// Point on the Earth, in degrees.
+/// Coordinates are always in range, so never NaN, see `GeoPoint::new`.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(try_from = "UncheckedGeoPoint")]
+pub struct GeoPoint {
+    latitude: f64,
+    longitude: f64,
+}
+
+impl Eq for GeoPoint {}
+
+/// Coordinates as stored, validated when converted to `GeoPoint`.
+#[derive(Deserialize)]
+struct UncheckedGeoPoint {
+    latitude: f64,
+    longitude: f64,
+}
+
+impl TryFrom<UncheckedGeoPoint> for GeoPoint {
+    type Error = String;
+
+    fn try_from(point: UncheckedGeoPoint) -> Result<Self, Self::Error> {
+        GeoPoint::new(point.latitude, point.longitude).ok_or_else(|| {
+            format!(
+                "Coordinates out of range: {}, {}",
+                point.latitude, point.longitude
+            )
+        })
+    }
+}
+
+impl GeoPoint {
+    /// Returns `None` if coordinates are out of range.
+    pub const fn new(latitude: f64, longitude: f64) -> Option<Self> {
+        let is_latitude_valid = -90.0 <= latitude && latitude <= 90.0;
+        let is_longitude_valid = -180.0 <= longitude && longitude <= 180.0;
+        match is_latitude_valid && is_longitude_valid {
+            true => Some(GeoPoint {
+                latitude,
+                longitude,
+            }),
+            false => None,
+        }
+    }
+
+    pub fn latitude(&self) -> f64 {
+        self.latitude
+    }
+
+    pub fn longitude(&self) -> f64 {
+        self.longitude
+    }
+
+    /// Parses "latitude, longitude" pair, as copied from most of the maps.
+    pub fn parse(text: &str) -> Option<Self> {
+        let (latitude, longitude) = text.split_once(',')?;
+        GeoPoint::new(
+            latitude.trim().parse().ok()?,
+            longitude.trim().parse().ok()?,
+        )
+    }
+
+    /// Great-circle distance in kilometers, computed with haversine formula.
+    pub fn distance_km(&self, other: &GeoPoint) -> f64 {
+        let (lat1, lat2) = (self.latitude.to_radians(), other.latitude.to_radians());
+        let delta_lat = lat2 - lat1;
+        let delta_lon = (other.longitude - self.longitude).to_radians();
+
+        let a = (delta_lat / 2.0).sin().powi(2)
+            + lat1.cos() * lat2.cos() * (delta_lon / 2.0).sin().powi(2);
+        2.0 * EARTH_RADIUS_KM * a.sqrt().asin()
+    }
+}
+
+impl fmt::Display for GeoPoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:.5}, {:.5}", self.latitude, self.longitude)
+    }
+}
+
+/// Area between two parallels and two meridians.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoundingBox {
+    pub south_west: GeoPoint,
+    pub north_east: GeoPoint,
+}
+
+impl BoundingBox {
+    /// Smallest box containing circle of `radius_km` around `center`.
+    pub fn around(center: &GeoPoint, radius_km: f64) -> Self {
+        let delta_lat = (radius_km / EARTH_RADIUS_KM).to_degrees();
+        let delta_lon = delta_lat / center.latitude.to_radians().cos().max(f64::EPSILON);
+        BoundingBox {
+            south_west: GeoPoint {
+                latitude: (center.latitude - delta_lat).max(-90.0),
+                longitude: (center.longitude - delta_lon).max(-180.0),
+            },
+            north_east: GeoPoint {
+                latitude: (center.latitude + delta_lat).min(90.0),
+                longitude: (center.longitude + delta_lon).min(180.0),
+            },
+        }
+    }
+
+    pub fn contains(&self, point: &GeoPoint) -> bool {
+        (self.south_west.latitude..=self.north_east.latitude).contains(&point.latitude)
+            && (self.south_west.longitude..=self.north_east.longitude).contains(&point.longitude)
+    }
+}
+
+impl Location {
+    pub fn distance_km_from(&self, point: &GeoPoint) -> Option<f64> {
+        self.coordinates
+            .as_ref()
+            .map(|coordinates| coordinates.distance_km(point))
+    }
+}
+
+impl Locations {
+    /// Locations with coordinates inside `bounding_box`.
+    pub fn within(&self, bounding_box: &BoundingBox) -> Vec<Location> {
+        self.locations
+            .values()
+            .filter(|location| {
+                location
+                    .coordinates
+                    .is_some_and(|coordinates| bounding_box.contains(&coordinates))
+            })
+            .cloned()
+            .collect()
+    }
+}
+
+/// Stable sort by distance from `point`, the nearest first.
+/// Locations without coordinates are kept at the end, in their previous order.
+pub fn sort_by_distance(ranked_locations: &mut [RankedLocation], point: &GeoPoint) {
+    ranked_locations.sort_by(|l1, l2| {
+        match (
+            l1.location.distance_km_from(point),
+            l2.location.distance_km_from(point),
+        ) {
+            (Some(d1), Some(d2)) => d1.total_cmp(&d2),
+            (d1, d2) => d1.is_none().cmp(&d2.is_none()),
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gazetteer::find_locality;
+    use crate::ranking::RankingWeights;
+    use crate::tags::TagPreference;
+
+    fn center_of(city: &str) -> GeoPoint {
+        find_locality(city).unwrap().center
+    }
+
+    #[test]
+    fn distances_and_bounding_boxes() {
+        let gdansk = center_of("Gdańsk");
+        let gdynia = center_of("Gdynia");
+        let distance = gdansk.distance_km(&gdynia);
+        assert!((20.0..22.0).contains(&distance), "{distance}");
+        assert_eq!(gdansk.distance_km(&gdansk), 0.0);
+
+        let around_gdansk = BoundingBox::around(&gdansk, 10.0);
+        assert!(around_gdansk.contains(&gdansk));
+        assert!(!around_gdansk.contains(&gdynia));
+        assert!(BoundingBox::around(&gdansk, 25.0).contains(&gdynia));
+        assert_eq!(GeoPoint::new(91.0, 0.0), None);
+        assert_eq!(GeoPoint::parse(&gdansk.to_string()), Some(gdansk));
+        assert_eq!(GeoPoint::parse("54.35"), None);
+    }
+
+    #[test]
+    fn coordinates_out_of_range_are_not_deserialized() {
+        let valid = bson::doc! { "latitude": 54.35, "longitude": 18.65 };
+        assert_eq!(bson::from_document(valid).ok(), GeoPoint::new(54.35, 18.65));
+        let invalid = bson::doc! { "latitude": f64::NAN, "longitude": 18.65 };
+        assert!(bson::from_document::<GeoPoint>(invalid).is_err());
+    }
+
+    #[test]
+    fn locations_are_sorted_by_distance() {
+        let mut locations = Locations::new();
+        for (name, coordinates) in [
+            ("Unknown", None),
+            ("Sopot", Some(center_of("Sopot"))),
+            ("Gdynia", Some(center_of("Gdynia"))),
+        ] {
+            locations.push_new(|loc| {
+                loc.name = name.into();
+                loc.coordinates = coordinates;
+            });
+        }
+
+        let mut ranked = locations.rank(&TagPreference::new(), &RankingWeights::new());
+        sort_by_distance(&mut ranked, &center_of("Gdańsk"));
+        let names: Vec<&str> = ranked
+            .iter()
+            .map(|r| r.location.name.default_text())
+            .collect();
+        assert_eq!(names, vec!["Sopot", "Gdynia", "Unknown"]);
+
+        let nearby = locations.within(&BoundingBox::around(&center_of("Gdańsk"), 15.0));
+        assert_eq!(nearby.len(), 1);
+    }
+}
@@ -0,0 +1,506 @@
+/*
+Copyright (C) 2025 Mateusz Mazur (Mazurel) <mateusz.mazur@e.email>
+
+This program is free software; you can redistribute it and/or
+modify it under the terms of the GNU General Public License
+as published by the Free Software Foundation; either version 2
+of the License, or (at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program; if not, see
+<https://www.gnu.org/licenses/>.
+*/
+
+use crate::{
+    error::Result,
+    locations::{Location, Locations},
+    merge::{MergeReport, MergeStrategy},
+    organisations::Organisation,
+};
+use serde::{Deserialize, Serialize};
+use std::ops::Deref;
+use uuid::Uuid;
+
+/// Maximal amount of operations kept in the journal,
+/// the oldest ones are forgotten first.
+pub const MAX_JOURNAL_LENGTH: usize = 100;
+
+/// Single mutation of the `Locations` database,
+/// holding enough data to be both reverted and applied again.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub enum Operation {
+    PushNew {
+        location: Box<Location>,
+    },
+    PushUpdate {
+        before: Box<Location>,
+        after: Box<Location>,
+    },
+    Remove {
+        location: Box<Location>,
+    },
+    /// Replacement of the whole database content, e.g. with a loaded file.
+    /// Only locations and organisations that differ are stored.
+    Reload {
+        /// Versions replaced or removed by the reload.
+        before: Vec<Location>,
+        /// Versions added or replacing the old ones.
+        after: Vec<Location>,
+        organisations_before: Vec<Organisation>,
+        organisations_after: Vec<Organisation>,
+    },
+    PushOrganisation {
+        before: Option<Organisation>,
+        after: Organisation,
+    },
+    RemoveOrganisation {
+        organisation: Organisation,
+    },
+    /// Operations done together, undone and redone at once.
+    Batch {
+        operations: Vec<Operation>,
+    },
+}
+
+impl Operation {
+    /// Content changes are written as new revisions, so that editors
+    /// holding a location from before undo/redo get a conflict.
+    fn apply(&self, locations: &mut Locations) {
+        match self {
+            Operation::PushNew { location } => {
+                locations.force_update(*location.clone());
+            }
+            Operation::PushUpdate { after, .. } => {
+                locations.force_update(*after.clone());
+            }
+            Operation::Remove { location } => {
+                let _ = locations.remove(*location.clone());
+            }
+            Operation::Reload {
+                before,
+                after,
+                organisations_before,
+                organisations_after,
+            } => {
+                replace_content(locations, before, after);
+                replace_organisations(locations, organisations_before, organisations_after);
+            }
+            Operation::PushOrganisation { after, .. } => {
+                locations.push_organisation(after.clone());
+            }
+            Operation::RemoveOrganisation { organisation } => {
+                locations.organisations.remove(&organisation.get_id());
+            }
+            Operation::Batch { operations } => {
+                for operation in operations.iter() {
+                    operation.apply(locations);
+                }
+            }
+        }
+    }
+
+    fn revert(&self, locations: &mut Locations) {
+        match self {
+            Operation::PushNew { location } => {
+                let _ = locations.remove(*location.clone());
+            }
+            Operation::PushUpdate { before, .. } => {
+                locations.force_update(*before.clone());
+            }
+            Operation::Remove { location } => {
+                locations.force_update(*location.clone());
+            }
+            Operation::Reload {
+                before,
+                after,
+                organisations_before,
+                organisations_after,
+            } => {
+                replace_content(locations, after, before);
+                replace_organisations(locations, organisations_after, organisations_before);
+            }
+            Operation::PushOrganisation { before, after } => match before {
+                Some(before) => {
+                    locations.push_organisation(before.clone());
+                }
+                None => {
+                    locations.organisations.remove(&after.get_id());
+                }
+            },
+            Operation::RemoveOrganisation { organisation } => {
+                locations.push_organisation(organisation.clone());
+            }
+            Operation::Batch { operations } => {
+                for operation in operations.iter().rev() {
+                    operation.revert(locations);
+                }
+            }
+        }
+    }
+}
+
+/// Removes `removed` locations and inserts `inserted` ones, exactly as they are.
+fn replace_content(locations: &mut Locations, removed: &[Location], inserted: &[Location]) {
+    for location in removed.iter() {
+        locations.locations.remove(&location.get_id());
+    }
+    for location in inserted.iter() {
+        locations
+            .locations
+            .insert(location.get_id(), location.clone());
+    }
+}
+
+fn replace_organisations(
+    locations: &mut Locations,
+    removed: &[Organisation],
+    inserted: &[Organisation],
+) {
+    for organisation in removed.iter() {
+        locations.organisations.remove(&organisation.get_id());
+    }
+    for organisation in inserted.iter() {
+        locations.push_organisation(organisation.clone());
+    }
+}
+
+/// History of operations done on the `Locations`, allowing to undo and redo them.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct Journal {
+    done: Vec<Operation>,
+    undone: Vec<Operation>,
+}
+
+impl Journal {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records new operation, it is no longer possible to redo undone operations.
+    pub fn record(&mut self, operation: Operation) {
+        self.undone.clear();
+        self.done.push(operation);
+        if self.done.len() > MAX_JOURNAL_LENGTH {
+            self.done.remove(0);
+        }
+    }
+
+    pub fn last_done(&self) -> Option<&Operation> {
+        self.done.last()
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.done.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.undone.is_empty()
+    }
+
+    /// Reverts the last done operation, returning it.
+    pub fn undo(&mut self, locations: &mut Locations) -> Option<Operation> {
+        let operation = self.done.pop()?;
+        operation.revert(locations);
+        self.undone.push(operation.clone());
+        Some(operation)
+    }
+
+    /// Applies again the last undone operation, returning it.
+    pub fn redo(&mut self, locations: &mut Locations) -> Option<Operation> {
+        let operation = self.undone.pop()?;
+        operation.apply(locations);
+        self.done.push(operation.clone());
+        Some(operation)
+    }
+}
+
+/// View of `Locations` recording all mutations in the `Journal`.
+/// Read-only API of `Locations` is available via `Deref`.
+pub struct JournaledLocations<'a> {
+    locations: &'a mut Locations,
+    journal: &'a mut Journal,
+    /// Operations of the ongoing `batch`.
+    batch: Option<Vec<Operation>>,
+}
+
+impl Deref for JournaledLocations<'_> {
+    type Target = Locations;
+
+    fn deref(&self) -> &Self::Target {
+        self.locations
+    }
+}
+
+impl<'a> JournaledLocations<'a> {
+    pub fn new(locations: &'a mut Locations, journal: &'a mut Journal) -> Self {
+        JournaledLocations {
+            locations,
+            journal,
+            batch: None,
+        }
+    }
+
+    pub fn journal(&self) -> &Journal {
+        self.journal
+    }
+
+    /// See `Locations::push_new`.
+    pub fn push_new<F>(&mut self, modify_loc_fn: F) -> Location
+    where
+        F: FnOnce(&mut Location),
+    {
+        let location = self.locations.push_new(modify_loc_fn);
+        self.record(Operation::PushNew {
+            location: Box::new(location.clone()),
+        });
+        location
+    }
+
+    /// See `Locations::push_new_nomodify`.
+    pub fn push_new_nomodify(&mut self) -> Location {
+        self.push_new(|_| {})
+    }
+
+    /// See `Locations::push_update`.
+    pub fn push_update(&mut self, new_location: Location) -> Result<Location> {
+        let before = self.current_version_of(&new_location);
+        let after = self.locations.push_update(new_location)?;
+        if let Some(before) = before {
+            self.record(Operation::PushUpdate {
+                before: Box::new(before),
+                after: Box::new(after.clone()),
+            });
+        }
+        Ok(after)
+    }
+
+    /// See `Locations::force_update`.
+    pub fn force_update(&mut self, new_location: Location) -> Location {
+        let before = self.current_version_of(&new_location);
+        let after = self.locations.force_update(new_location);
+        self.record(match before {
+            Some(before) => Operation::PushUpdate {
+                before: Box::new(before),
+                after: Box::new(after.clone()),
+            },
+            None => Operation::PushNew {
+                location: Box::new(after.clone()),
+            },
+        });
+        after
+    }
+
+    /// See `Locations::remove`.
+    pub fn remove(&mut self, location: Location) -> Result<Location> {
+        let removed = self.locations.remove(location)?;
+        self.record(Operation::Remove {
+            location: Box::new(removed.clone()),
+        });
+        Ok(removed)
+    }
+
+    /// See `Locations::push_organisation`.
+    pub fn push_organisation(&mut self, organisation: Organisation) {
+        let before = self.locations.push_organisation(organisation.clone());
+        self.record(Operation::PushOrganisation {
+            before,
+            after: organisation,
+        });
+    }
+
+    /// See `Locations::remove_organisation`.
+    pub fn remove_organisation(&mut self, organisation_id: Uuid) -> Result<Organisation> {
+        let organisation = self.locations.remove_organisation(organisation_id)?;
+        self.record(Operation::RemoveOrganisation {
+            organisation: organisation.clone(),
+        });
+        Ok(organisation)
+    }
+
+    /// Replaces the whole database content with `new_locations`.
+    pub fn reload(&mut self, new_locations: Locations) {
+        let old_locations = std::mem::replace(self.locations, new_locations);
+        let new_locations = &*self.locations;
+        let changed_locations = |from: &Locations, to: &Locations| -> Vec<Location> {
+            from.locations
+                .values()
+                .filter(|location| to.locations.get(&location.get_id()) != Some(location))
+                .cloned()
+                .collect()
+        };
+        let changed_organisations = |from: &Locations, to: &Locations| -> Vec<Organisation> {
+            from.organisations
+                .values()
+                .filter(|organisation| {
+                    to.organisations.get(&organisation.get_id()) != Some(organisation)
+                })
+                .cloned()
+                .collect()
+        };
+        let operation = Operation::Reload {
+            before: changed_locations(&old_locations, new_locations),
+            after: changed_locations(new_locations, &old_locations),
+            organisations_before: changed_organisations(&old_locations, new_locations),
+            organisations_after: changed_organisations(new_locations, &old_locations),
+        };
+        self.record(operation);
+    }
+
+    /// See `Locations::merge`, whole merge is recorded as a single reload.
+    pub fn merge(&mut self, other: &Locations, strategy: MergeStrategy) -> MergeReport {
+        let mut merged_locations = self.locations.clone();
+        let report = merged_locations.merge(other, strategy);
+        self.reload(merged_locations);
+        report
+    }
+
+    /// Runs `batch_fn`, recording all of its operations as a single `Operation::Batch`.
+    pub fn batch<T, F>(&mut self, batch_fn: F) -> T
+    where
+        F: FnOnce(&mut JournaledLocations) -> T,
+    {
+        let outer_batch = self.batch.replace(vec![]);
+        let result = batch_fn(self);
+        let operations = std::mem::replace(&mut self.batch, outer_batch).unwrap_or_default();
+        if !operations.is_empty() {
+            self.record(Operation::Batch { operations });
+        }
+        result
+    }
+
+    pub fn undo(&mut self) -> Option<Operation> {
+        self.journal.undo(self.locations)
+    }
+
+    pub fn redo(&mut self) -> Option<Operation> {
+        self.journal.redo(self.locations)
+    }
+
+    fn record(&mut self, operation: Operation) {
+        match &mut self.batch {
+            Some(batch) => batch.push(operation),
+            None => self.journal.record(operation),
+        }
+    }
+
+    fn current_version_of(&self, location: &Location) -> Option<Location> {
+        self.locations.locations.get(&location.get_id()).cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn name_of(locations: &Locations, location: &Location) -> String {
+        locations.locations[&location.get_id()].name.to_string()
+    }
+
+    #[test]
+    fn undo_and_redo_every_operation() {
+        let mut locations = Locations::new();
+        let mut journal = Journal::new();
+        let mut journaled = JournaledLocations::new(&mut locations, &mut journal);
+
+        let location = journaled.push_new(|loc| loc.name = "Example".into());
+        let mut renamed = location.clone();
+        renamed.name = "Renamed".into();
+        journaled.push_update(renamed).unwrap();
+        journaled.remove(location.clone()).unwrap();
+        assert!(!journaled.contains(&location));
+
+        journaled.undo();
+        assert_eq!(name_of(&journaled, &location), "Renamed");
+        journaled.undo();
+        assert_eq!(name_of(&journaled, &location), "Example");
+        journaled.undo();
+        assert!(!journaled.contains(&location));
+        assert!(journaled.undo().is_none());
+
+        journaled.redo();
+        journaled.redo();
+        assert_eq!(name_of(&journaled, &location), "Renamed");
+        assert!(journaled.journal().can_redo());
+
+        journaled.reload(Locations::new());
+        assert!(!journaled.journal().can_redo());
+        journaled.undo();
+        assert!(journaled.contains(&location));
+    }
+
+    #[test]
+    fn reload_stores_only_changed_locations() {
+        let mut locations = Locations::new();
+        let mut journal = Journal::new();
+        let mut journaled = JournaledLocations::new(&mut locations, &mut journal);
+        let kept = journaled.push_new(|loc| loc.name = "Kept".into());
+        let removed = journaled.push_new(|loc| loc.name = "Removed".into());
+
+        let mut new_locations = journaled.clone();
+        new_locations.remove(removed.clone()).unwrap();
+        let added = new_locations.push_new(|loc| loc.name = "Added".into());
+        let before_reload = journaled.clone();
+        journaled.reload(new_locations.clone());
+        assert_eq!(
+            journaled.journal().last_done(),
+            Some(&Operation::Reload {
+                before: vec![removed],
+                after: vec![added],
+                organisations_before: vec![],
+                organisations_after: vec![],
+            })
+        );
+        assert!(journaled.contains(&kept));
+
+        journaled.undo();
+        assert_eq!(*journaled, before_reload);
+        journaled.redo();
+        assert_eq!(*journaled, new_locations);
+    }
+
+    #[test]
+    fn batch_is_undone_at_once() {
+        let mut locations = Locations::new();
+        let mut journal = Journal::new();
+        let mut journaled = JournaledLocations::new(&mut locations, &mut journal);
+
+        let first = journaled.push_new_nomodify();
+        let second = journaled.batch(|journaled| {
+            journaled.remove(first.clone()).unwrap();
+            journaled.push_new_nomodify()
+        });
+        assert!(matches!(
+            journaled.journal().last_done(),
+            Some(Operation::Batch { operations }) if operations.len() == 2
+        ));
+
+        journaled.undo();
+        assert!(journaled.contains(&first));
+        assert!(!journaled.contains(&second));
+        journaled.redo();
+        assert!(!journaled.contains(&first));
+        assert!(journaled.contains(&second));
+    }
+
+    #[test]
+    fn journal_length_is_limited() {
+        let mut locations = Locations::new();
+        let mut journal = Journal::new();
+        let mut journaled = JournaledLocations::new(&mut locations, &mut journal);
+
+        for _ in 0..MAX_JOURNAL_LENGTH + 10 {
+            journaled.push_new_nomodify();
+        }
+
+        let mut undone = 0;
+        while journaled.undo().is_some() {
+            undone += 1;
+        }
+        assert_eq!(undone, MAX_JOURNAL_LENGTH);
+    }
+}
@@ -16,10 +16,32 @@ along with this program; if not, see
 <https://www.gnu.org/licenses/>.
 */
 
+pub mod accessibility;
+pub mod address;
+pub mod audit;
+pub mod bulk;
 pub mod contact;
+pub mod diff;
+pub mod duplicates;
+pub mod error;
+pub mod fuzzy;
+pub mod gazetteer;
+pub mod geo;
+pub mod journal;
+pub mod localized;
 pub mod locations;
+pub mod merge;
+pub mod migrations;
+pub mod organisations;
+pub mod publication;
+pub mod query;
+pub mod ranking;
+pub mod search;
+pub mod service_terms;
 pub mod tags;
 pub mod time;
+pub mod trash;
+pub mod validation;
 
 // Load I18n macro, for allow you use `t!` macro in anywhere.
 #[macro_use]
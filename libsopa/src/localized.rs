@@ -0,0 +1,188 @@
+/*
+Copyright (C) 2025 Mateusz Mazur (Mazurel) <mateusz.mazur@e.email>
+
+This program is free software; you can redistribute it and/or
+modify it under the terms of the GNU General Public License
+as published by the Free Software Foundation; either version 2
+of the License, or (at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program; if not, see
+<https://www.gnu.org/licenses/>.
+*/
+
+//! Content of locations written in many languages.
+
+use serde::{Deserialize, Deserializer, Serialize};
+use std::{collections::BTreeMap, fmt};
+
+/// Locale the content is written in first, used when translation is missing.
+pub const DEFAULT_CONTENT_LOCALE: &str = "pl";
+
+/// Locales content can be translated to, may include locales without translated UI.
+pub const CONTENT_LOCALES: &[&str] = &["pl", "en", "uk"];
+
+/// Content locale matching the current locale of `rust_i18n`,
+/// `DEFAULT_CONTENT_LOCALE` when the UI is in a language content is not written in.
+pub fn current_content_locale() -> &'static str {
+    let ui_locale = rust_i18n::locale();
+    CONTENT_LOCALES
+        .iter()
+        .copied()
+        .find(|locale| **locale == *ui_locale)
+        .unwrap_or(DEFAULT_CONTENT_LOCALE)
+}
+
+/// Text keyed by locale, e.g. "pl" or "en". Empty translations are not stored.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+#[serde(transparent)]
+pub struct LocalizedText {
+    translations: BTreeMap<String, String>,
+}
+
+impl LocalizedText {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Translation to exactly `locale`, without fallback.
+    pub fn translation(&self, locale: &str) -> Option<&str> {
+        self.translations.get(locale).map(String::as_str)
+    }
+
+    /// Text in `locale`, falling back to `DEFAULT_CONTENT_LOCALE`
+    /// and then to any other translation.
+    pub fn get(&self, locale: &str) -> &str {
+        self.translation(locale)
+            .or_else(|| self.translation(DEFAULT_CONTENT_LOCALE))
+            .or_else(|| self.translations.values().next().map(String::as_str))
+            .unwrap_or_default()
+    }
+
+    /// Text in the current locale of `rust_i18n`.
+    pub fn current(&self) -> &str {
+        self.get(&rust_i18n::locale())
+    }
+
+    pub fn default_text(&self) -> &str {
+        self.get(DEFAULT_CONTENT_LOCALE)
+    }
+
+    /// Sets translation to `locale`, empty `text` removes it.
+    pub fn set<S: ToString>(&mut self, locale: &str, text: S) {
+        let text = text.to_string();
+        match text.trim().is_empty() {
+            true => self.translations.remove(locale),
+            false => self.translations.insert(locale.to_string(), text),
+        };
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.translations.is_empty()
+    }
+
+    /// Locales with translation, in alphabetical order.
+    pub fn locales(&self) -> impl Iterator<Item = &str> {
+        self.translations.keys().map(String::as_str)
+    }
+
+    /// Distinct texts of all translations.
+    pub fn texts(&self) -> Vec<&str> {
+        let mut texts: Vec<&str> = self.translations.values().map(String::as_str).collect();
+        texts.sort();
+        texts.dedup();
+        texts
+    }
+
+    /// `CONTENT_LOCALES` without translation, none if there is no text at all.
+    pub fn missing_locales(&self) -> Vec<&'static str> {
+        match self.is_empty() {
+            true => vec![],
+            false => CONTENT_LOCALES
+                .iter()
+                .copied()
+                .filter(|locale| self.translation(locale).is_none())
+                .collect(),
+        }
+    }
+
+    /// Accepts both localized text and a plain string stored before
+    /// the content got localized, e.g. in the IndexedDB.
+    pub fn deserialize_localized_or_plain<'de, D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum AnyText {
+            Localized(BTreeMap<String, String>),
+            Plain(String),
+        }
+        Ok(match AnyText::deserialize(deserializer)? {
+            AnyText::Localized(translations) => LocalizedText { translations },
+            AnyText::Plain(text) => LocalizedText::from(text),
+        })
+    }
+}
+
+/// Text written in the `DEFAULT_CONTENT_LOCALE`.
+impl From<String> for LocalizedText {
+    fn from(text: String) -> Self {
+        let mut localized = LocalizedText::new();
+        localized.set(DEFAULT_CONTENT_LOCALE, text);
+        localized
+    }
+}
+
+impl From<&str> for LocalizedText {
+    fn from(text: &str) -> Self {
+        LocalizedText::from(text.to_string())
+    }
+}
+
+/// Text in the current locale, see `LocalizedText::current`.
+impl fmt::Display for LocalizedText {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.current())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn translations_fall_back_to_default_locale() {
+        let mut text = LocalizedText::from("Schronisko");
+        text.set("en", "Shelter");
+        assert_eq!(text.get("en"), "Shelter");
+        assert_eq!(text.get("uk"), "Schronisko");
+        assert_eq!(text.missing_locales(), vec!["uk"]);
+
+        text.set("pl", "  ");
+        assert_eq!(text.translation("pl"), None);
+        assert_eq!(text.get("uk"), "Shelter");
+        assert_eq!(LocalizedText::new().get("pl"), "");
+        assert!(LocalizedText::new().missing_locales().is_empty());
+    }
+
+    #[test]
+    fn plain_text_is_deserialized_as_default_locale() {
+        let document = bson::doc! { "plain": "Schronisko", "localized": { "en": "Shelter" } };
+        #[derive(Deserialize)]
+        struct Texts {
+            #[serde(deserialize_with = "LocalizedText::deserialize_localized_or_plain")]
+            plain: LocalizedText,
+            #[serde(deserialize_with = "LocalizedText::deserialize_localized_or_plain")]
+            localized: LocalizedText,
+        }
+        let texts: Texts = bson::from_document(document).unwrap();
+        assert_eq!(texts.plain, LocalizedText::from("Schronisko"));
+        assert_eq!(texts.localized.get("pl"), "Shelter");
+    }
+}
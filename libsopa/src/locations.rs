@@ -16,61 +16,195 @@ along with this program; if not, see
 <https://www.gnu.org/licenses/>.
 */
 
-use crate::{contact::ContactMethods, tags::Tags, time::OpenedHours};
+use crate::{
+    accessibility::Accessibility,
+    address::Address,
+    audit::{self, Timestamp},
+    contact::ContactMethods,
+    error::{Error, Result},
+    geo::GeoPoint,
+    localized::LocalizedText,
+    migrations,
+    organisations::Organisation,
+    publication::PublicationState,
+    service_terms::ServiceTerms,
+    tags::Tags,
+    time::OpenedHours,
+};
 #[cfg(feature = "wasm")]
 use deli::Model;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use uuid::Uuid;
 
+/// Counter of commited changes of a single location.
+pub type Revision = u32;
+
+/// Number of the most recent revision IDs remembered by a location.
+const MAX_REVISION_IDS: usize = 100;
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "wasm", derive(Model))]
 pub struct Location {
     #[cfg_attr(feature = "wasm", deli(key))]
     id: Uuid,
-    pub name: String,
+    #[serde(deserialize_with = "LocalizedText::deserialize_localized_or_plain")]
+    pub name: LocalizedText,
     pub tags: Tags,
-    pub address: String,
-    #[serde(default)]
-    pub description: String,
+    #[serde(deserialize_with = "Address::deserialize_structured_or_free_form")]
+    pub address: Address,
+    #[serde(
+        default,
+        deserialize_with = "LocalizedText::deserialize_localized_or_plain"
+    )]
+    pub description: LocalizedText,
     #[serde(default)]
     pub contact_methods: ContactMethods,
-    // NOTE: Starting from here fields are not backwards compatible :/
-    //       As a simple W/A for now we can use Serde default.
-    // TODO: Think of some better migration mechanism, like Diesel or something.
+    // NOTE: Old database files get missing fields filled in by `migrations`,
+    //       Serde default is kept for locations restored from IndexedDB,
+    //       which are stored without schema version.
     #[serde(default)]
     pub opened_hours: OpenedHours,
+    #[serde(default)]
+    pub coordinates: Option<GeoPoint>,
+    #[serde(default)]
+    pub accessibility: Accessibility,
+    #[serde(default)]
+    pub service_terms: ServiceTerms,
+    /// Organisation this location is a branch of, see `organisations`.
+    #[serde(default)]
+    pub organisation: Option<Uuid>,
+    /// Maintained by `Locations`, see `audit`.
+    #[serde(default)]
+    pub created_at: Option<Timestamp>,
+    #[serde(default)]
+    pub updated_at: Option<Timestamp>,
+    /// Name of the editor of the last update, see `audit::set_editor_name`.
+    #[serde(default)]
+    pub updated_by: String,
+    /// When the content was last checked, e.g. by calling the location.
+    #[serde(default)]
+    pub last_verified_at: Option<Timestamp>,
+    /// Changed only by `Location::transition_to`.
+    #[serde(default = "PublicationState::of_existing_location")]
+    pub(crate) publication_state: PublicationState,
+    /// Set when the location is in the trash, see `trash`.
+    #[serde(default)]
+    pub(crate) deleted_at: Option<Timestamp>,
+    #[serde(default)]
+    revision: Revision,
+    /// IDs of the most recent revisions, the current one last.
+    /// Used by `merge` to find out, whether two versions of the location
+    /// share a common base, see `Location::descends_from`.
+    #[serde(default)]
+    revision_ids: Vec<Uuid>,
 }
 
 impl Location {
     pub fn get_id(&self) -> Uuid {
         self.id
     }
+
+    /// Revision of the database entry this location is based on.
+    pub fn get_revision(&self) -> Revision {
+        self.revision
+    }
+
+    /// Whether this location is `ancestor` or was derived from it by further edits.
+    /// Locations stored before revision IDs were tracked fall back to
+    /// comparing revision counters.
+    pub fn descends_from(&self, ancestor: &Location) -> bool {
+        match ancestor.revision_ids.last() {
+            Some(ancestor_id) => self.revision_ids.contains(ancestor_id),
+            None => self.revision >= ancestor.revision,
+        }
+    }
+
+    /// Compares content of the locations, ignoring revisions and audit metadata.
+    pub fn has_same_content(&self, other: &Location) -> bool {
+        let strip = |location: &Location| Location {
+            revision: 0,
+            revision_ids: Vec::new(),
+            updated_at: None,
+            updated_by: String::new(),
+            ..location.clone()
+        };
+        strip(self) == strip(other)
+    }
+
+    /// Returns this location with the history of `other` version appended,
+    /// so that it descends from both of them.
+    pub(crate) fn rebased_onto(mut self, other: &Location) -> Location {
+        let current_id = self.revision_ids.pop();
+        for id in other.revision_ids.iter() {
+            if !self.revision_ids.contains(id) {
+                self.revision_ids.push(*id);
+            }
+        }
+        self.revision_ids.extend(current_id);
+        self.trim_revision_ids();
+        self.revision = self.revision.max(other.revision);
+        self
+    }
+
+    fn start_new_revision(&mut self) {
+        self.revision_ids.push(Uuid::new_v4());
+        self.trim_revision_ids();
+    }
+
+    fn trim_revision_ids(&mut self) {
+        let excess = self.revision_ids.len().saturating_sub(MAX_REVISION_IDS);
+        self.revision_ids.drain(..excess);
+    }
 }
 
 impl Default for Location {
     fn default() -> Self {
         Location {
             id: Uuid::new_v4(),
-            name: String::from(""),
+            name: LocalizedText::new(),
             tags: Tags::new(),
-            address: String::from(""),
-            description: String::from(""),
+            address: Address::default(),
+            description: LocalizedText::new(),
             contact_methods: ContactMethods::default(),
             opened_hours: OpenedHours::default(),
+            coordinates: None,
+            accessibility: Accessibility::default(),
+            service_terms: ServiceTerms::default(),
+            organisation: None,
+            created_at: None,
+            updated_at: None,
+            updated_by: String::new(),
+            last_verified_at: None,
+            publication_state: PublicationState::Draft,
+            deleted_at: None,
+            revision: 0,
+            revision_ids: Vec::new(),
         }
     }
 }
 
+/// State of the location in the database, as seen by `Locations::fetch_update`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[allow(clippy::large_enum_variant)]
+pub enum LocationUpdate {
+    Unchanged,
+    Changed(Location),
+    Deleted,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct Locations {
     pub locations: HashMap<Uuid, Location>,
+    #[serde(default)]
+    pub organisations: HashMap<Uuid, Organisation>,
 }
 
 impl From<Vec<Location>> for Locations {
     fn from(value: Vec<Location>) -> Self {
         Locations {
             locations: value.into_iter().map(|loc| (loc.id, loc)).collect(),
+            organisations: HashMap::new(),
         }
     }
 }
@@ -103,61 +237,26 @@ impl Locations {
             .collect()
     }
 
-    pub fn all_locations_in_order(&self, tags_preference: &Tags) -> Vec<Location> {
-        let mut locations = self.locations_in_random_order();
-        let mut locations_overlaps: Vec<_> = locations
-            .clone()
-            .into_iter()
-            .map(|loc| loc.tags.overlap(tags_preference))
-            .collect();
-
-        let mut locations_result = Vec::new();
-
-        while locations.len() > 0 {
-            let i = locations_overlaps
-                .iter()
-                .enumerate()
-                .min_by(|(_, o1), (_, o2)| o2.total_cmp(o1))
-                .expect("Locations should not be empty due to loop condition.")
-                .0;
-
-            locations_overlaps.remove(i);
-            locations_result.push(locations.remove(i));
-        }
-
-        locations_result
-    }
-
     /// Fetch update for `old_location`.
-    /// This function returns new instance of Location with updated content,
+    /// This function returns state of the location in the database,
     /// it is assummed the function will be used in the following fashion:
     ///
     /// ```rust
     /// # use libsopa::locations::*;
     /// # let mut locations = Locations::new();
     /// # let mut old_location = locations.push_new(|loc| {
-    /// #    loc.name = "Example".to_string();
+    /// #    loc.name = "Example".into();
     /// # });
-    /// if let Some(new_location) = locations.fetch_update(&old_location) {
+    /// if let LocationUpdate::Changed(new_location) = locations.fetch_update(&old_location) {
     ///     // Most likely you will want to update old location now
     ///     old_location = new_location;
     /// }
     /// ```
-    ///
-    /// **SAFETY:**
-    /// Current implementation doesn't distinguish missing location in the DB
-    /// with case when location wasn't changed.
-    /// That is why, for now we panic in case of location missing in the DB,
-    /// in the future we will need to return custom result type.
-    pub fn fetch_update(&self, old_location: &Location) -> Option<Location> {
-        if let Some(location) = self.locations.get(&old_location.get_id()) {
-            if old_location != location {
-                Some(location.clone())
-            } else {
-                None
-            }
-        } else {
-            panic!("Location is not in the DB at all, this API doesn't support this case for now!");
+    pub fn fetch_update(&self, old_location: &Location) -> LocationUpdate {
+        match self.locations.get(&old_location.get_id()) {
+            Some(location) if location == old_location => LocationUpdate::Unchanged,
+            Some(location) => LocationUpdate::Changed(location.clone()),
+            None => LocationUpdate::Deleted,
         }
     }
 
@@ -167,25 +266,74 @@ impl Locations {
 
     /// Push state of `new_location` to the database, modyfing permanently
     /// content of the `Location` in the database, based on location ID.
+    /// Returns location as stored in the database, with bumped revision,
+    /// that should be used for further edits.
     ///
-    /// **WARNING**:
-    /// Pushing update may result in data loss if the same location
-    /// was modified "in the meantime". Please try to fetch current
-    /// location version from via `fetch_update` before modyfing location.
-    pub fn push_update(&mut self, new_location: Location) {
-        self.locations.insert(new_location.get_id(), new_location);
+    /// Update is rejected with `Error::Conflict` if the location in the database
+    /// was modified "in the meantime", that is, when `new_location` is not based
+    /// on the current revision. Use `force_update` to overwrite it anyway.
+    pub fn push_update(&mut self, new_location: Location) -> Result<Location> {
+        let current_location = self
+            .locations
+            .get(&new_location.get_id())
+            .ok_or(Error::MissingLocation(new_location.get_id()))?;
+
+        if current_location.revision != new_location.revision {
+            return Err(Error::Conflict {
+                stale: Box::new(new_location),
+                current: Box::new(current_location.clone()),
+            });
+        }
+
+        Ok(self.force_update(new_location))
+    }
+
+    /// Works like `push_update`, but overwrites location in the database
+    /// regardless of its revision.
+    pub fn force_update(&mut self, mut new_location: Location) -> Location {
+        new_location.updated_at = Some(audit::now());
+        new_location.updated_by = audit::editor_name();
+        let current_revision = self
+            .locations
+            .get(&new_location.get_id())
+            .map(|location| location.revision)
+            .unwrap_or(new_location.revision);
+        new_location.revision = current_revision.max(new_location.revision) + 1;
+        new_location.start_new_revision();
+        self.locations
+            .insert(new_location.get_id(), new_location.clone());
+        new_location
+    }
+
+    /// Inserts `location` read from a persistent storage as it is,
+    /// unless the database already holds a newer revision of it.
+    /// Returns whether the location was inserted.
+    pub fn restore(&mut self, location: Location) -> bool {
+        let is_outdated = self
+            .locations
+            .get(&location.get_id())
+            .is_some_and(|current| current.revision > location.revision);
+        if !is_outdated {
+            self.locations.insert(location.get_id(), location);
+        }
+        !is_outdated
     }
 
     /// Creates new location instance in the database,
     /// then consumes `modify_loc_fn` to change this location initial content.
     /// Afterwards commits the changes.
+    /// Creation metadata is set before `modify_loc_fn`, so that it can be overwritten.
     pub fn push_new<F>(&mut self, modify_loc_fn: F) -> Location
     where
         F: FnOnce(&mut Location),
     {
         let mut location = Location::default();
+        location.created_at = Some(audit::now());
+        location.updated_at = location.created_at;
+        location.updated_by = audit::editor_name();
+        location.start_new_revision();
         modify_loc_fn(&mut location);
-        self.push_update(location.clone());
+        self.locations.insert(location.get_id(), location.clone());
         location
     }
 
@@ -195,16 +343,38 @@ impl Locations {
         self.push_new(|_| {})
     }
 
-    pub fn remove(&mut self, location: Location) {
-        self.locations.remove(&location.get_id());
+    /// Removes location from the database permanently, returning removed instance.
+    /// Prefer `move_to_trash` for the deletions made by editors.
+    pub fn remove(&mut self, location: Location) -> Result<Location> {
+        self.locations
+            .remove(&location.get_id())
+            .ok_or(Error::MissingLocation(location.get_id()))
     }
 
-    pub fn to_bin_data(&self) -> Vec<u8> {
-        bson::to_vec(self).unwrap()
+    /// Serializes database, together with the current schema version.
+    pub fn to_bin_data(&self) -> Result<Vec<u8>> {
+        let mut document = bson::to_document(self)?;
+        migrations::set_schema_version(&mut document, migrations::CURRENT_SCHEMA_VERSION);
+        Ok(bson::to_vec(&document)?)
     }
 
-    pub fn from_bin_data(bin_data: Vec<u8>) -> Self {
-        bson::from_slice(&bin_data).unwrap()
+    /// Deserializes database, running all migrations required
+    /// to bring it from its schema version to the current one.
+    pub fn from_bin_data(bin_data: Vec<u8>) -> Result<Self> {
+        let mut document: bson::Document = bson::from_slice(&bin_data)?;
+        migrations::migrate_to_current_version(&mut document)?;
+        document.remove(migrations::SCHEMA_VERSION_KEY);
+
+        let locations: Locations = bson::from_document(document)?;
+        for (key, location) in locations.locations.iter() {
+            if *key != location.get_id() {
+                return Err(Error::IdMismatch {
+                    key: *key,
+                    id: location.get_id(),
+                });
+            }
+        }
+        Ok(locations)
     }
 }
 
@@ -216,23 +386,107 @@ mod tests {
     fn creating_locations_and_working_with_one_location() {
         let mut locations = Locations::new();
         let location = locations.push_new(|loc| {
-            loc.name = "Example".to_string();
+            loc.name = "Example".into();
         });
-        assert_eq!(location.name, "Example".to_string());
+        assert_eq!(location.name.default_text(), "Example");
 
         {
             let mut modified_location = location.clone();
-            modified_location.name = "Not an Example".to_string();
-            locations.push_update(modified_location);
+            modified_location.name = "Not an Example".into();
+            locations.push_update(modified_location).unwrap();
         }
 
         // The original object should still contain old value
-        assert_eq!(location.name, "Example".to_string());
+        assert_eq!(location.name.default_text(), "Example");
         // But after fetching update, it should have a new value
-        let new_location = locations
-            .fetch_update(&location)
-            .expect("There should be an update to the location");
+        let LocationUpdate::Changed(new_location) = locations.fetch_update(&location) else {
+            panic!("There should be an update to the location");
+        };
         assert_ne!(location, new_location);
-        assert_eq!(new_location.name, "Not an Example".to_string());
+        assert_eq!(new_location.name.default_text(), "Not an Example");
+        assert_eq!(
+            locations.fetch_update(&new_location),
+            LocationUpdate::Unchanged
+        );
+
+        locations.remove(new_location.clone()).unwrap();
+        assert_eq!(
+            locations.fetch_update(&new_location),
+            LocationUpdate::Deleted
+        );
+        assert_eq!(
+            locations.remove(new_location.clone()),
+            Err(Error::MissingLocation(new_location.get_id()))
+        );
+    }
+
+    #[test]
+    fn stale_update_is_rejected_with_conflict() {
+        let mut locations = Locations::new();
+        let location = locations.push_new_nomodify();
+
+        let mut first_edit = location.clone();
+        first_edit.name = "First".into();
+        let mut second_edit = location.clone();
+        second_edit.name = "Second".into();
+
+        let stored = locations.push_update(first_edit).unwrap();
+        assert_eq!(stored.get_revision(), location.get_revision() + 1);
+
+        assert_eq!(
+            locations.push_update(second_edit.clone()),
+            Err(Error::Conflict {
+                stale: Box::new(second_edit.clone()),
+                current: Box::new(stored.clone()),
+            })
+        );
+
+        // Overwriting is still possible on explicit request
+        let forced = locations.force_update(second_edit);
+        assert_eq!(forced.name.default_text(), "Second");
+        assert!(forced.get_revision() > stored.get_revision());
+        assert!(!locations.restore(stored));
+    }
+
+    #[test]
+    fn serialization_roundtrip_keeps_locations() {
+        let mut locations = Locations::new();
+        locations.push_new(|loc| {
+            loc.name = "Example".into();
+            loc.tags.define_tag("LGBT");
+        });
+
+        let bin_data = locations.to_bin_data().unwrap();
+        assert_eq!(Locations::from_bin_data(bin_data), Ok(locations));
+    }
+
+    #[test]
+    fn corrupted_data_is_reported() {
+        let result = Locations::from_bin_data(vec![0xde, 0xad, 0xbe, 0xef]);
+        assert!(matches!(result, Err(Error::CorruptData(_))));
+    }
+
+    #[test]
+    fn location_stored_under_wrong_key_is_reported() {
+        let location = Location::default();
+        let wrong_key = Uuid::new_v4();
+        let mut locations = Locations::new();
+        locations.locations.insert(wrong_key, location.clone());
+
+        let bin_data = locations.to_bin_data().unwrap();
+        assert_eq!(
+            Locations::from_bin_data(bin_data),
+            Err(Error::IdMismatch {
+                key: wrong_key,
+                id: location.get_id()
+            })
+        );
+    }
+
+    #[test]
+    fn initial_database_loads_through_migrations() {
+        let bin_data = include_bytes!("../../frontend/src/initial_database.bson").to_vec();
+        let locations = Locations::from_bin_data(bin_data).unwrap();
+        assert!(!locations.locations.is_empty());
     }
 }
@@ -0,0 +1,279 @@
+/*
+Copyright (C) 2025 Mateusz Mazur (Mazurel) <mateusz.mazur@e.email>
+
+This program is free software; you can redistribute it and/or
+modify it under the terms of the GNU General Public License
+as published by the Free Software Foundation; either version 2
+of the License, or (at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program; if not, see
+<https://www.gnu.org/licenses/>.
+*/
+
+use crate::{
+    locations::{Location, Locations},
+    organisations::Organisation,
+};
+
+/// Decides which version is kept, when location was changed on both sides.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MergeStrategy {
+    #[default]
+    KeepOurs,
+    TakeTheirs,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MergeConflict {
+    pub ours: Location,
+    pub theirs: Location,
+}
+
+impl MergeConflict {
+    pub fn resolve(&self, strategy: MergeStrategy) -> &Location {
+        match strategy {
+            MergeStrategy::KeepOurs => &self.ours,
+            MergeStrategy::TakeTheirs => &self.theirs,
+        }
+    }
+}
+
+/// Organisation with different content in both databases.
+/// Organisations are not versioned, so any difference is a conflict.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrganisationConflict {
+    pub ours: Organisation,
+    pub theirs: Organisation,
+}
+
+impl OrganisationConflict {
+    pub fn resolve(&self, strategy: MergeStrategy) -> &Organisation {
+        match strategy {
+            MergeStrategy::KeepOurs => &self.ours,
+            MergeStrategy::TakeTheirs => &self.theirs,
+        }
+    }
+}
+
+/// Outcome of merging other database into ours.
+/// Locations are matched by their IDs and compared by their revision history:
+/// version derived from the other one wins, while versions edited separately
+/// since their common base mean the location was changed on both sides.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct MergeReport {
+    pub strategy: MergeStrategy,
+    /// Locations that were only in the other database.
+    pub added: Vec<Location>,
+    /// Locations edited further in the other database.
+    pub updated: Vec<Location>,
+    /// Locations edited further in our database, ours are kept.
+    pub outdated: Vec<Location>,
+    /// Locations changed on both sides, resolved with `strategy`.
+    pub conflicts: Vec<MergeConflict>,
+    /// Amount of locations identical on both sides.
+    pub unchanged: usize,
+    /// Organisations that were only in the other database.
+    pub added_organisations: Vec<Organisation>,
+    /// Organisations different on both sides, resolved with `strategy`.
+    pub organisation_conflicts: Vec<OrganisationConflict>,
+}
+
+impl MergeReport {
+    /// Whether applying the merge changes anything in our database.
+    pub fn is_noop(&self) -> bool {
+        let has_conflicts = !self.conflicts.is_empty() || !self.organisation_conflicts.is_empty();
+        self.added.is_empty()
+            && self.updated.is_empty()
+            && self.added_organisations.is_empty()
+            && (!has_conflicts || self.strategy == MergeStrategy::KeepOurs)
+    }
+}
+
+impl Locations {
+    /// Computes what `merge` would do, without modifying the database.
+    pub fn plan_merge(&self, other: &Locations, strategy: MergeStrategy) -> MergeReport {
+        let mut report = MergeReport {
+            strategy,
+            ..Default::default()
+        };
+
+        let mut their_locations = other.locations_in_random_order();
+        their_locations.sort_by(|l1, l2| l1.name.default_text().cmp(l2.name.default_text()));
+
+        for theirs in their_locations {
+            match self.locations.get(&theirs.get_id()) {
+                None => report.added.push(theirs),
+                Some(ours) if *ours == theirs || ours.has_same_content(&theirs) => {
+                    report.unchanged += 1
+                }
+                Some(ours) => match (theirs.descends_from(ours), ours.descends_from(&theirs)) {
+                    (true, false) => report.updated.push(theirs),
+                    (false, true) => report.outdated.push(theirs),
+                    _ => report.conflicts.push(MergeConflict {
+                        ours: ours.clone(),
+                        theirs,
+                    }),
+                },
+            }
+        }
+
+        for theirs in other.organisations_in_order() {
+            match self.organisations.get(&theirs.get_id()) {
+                None => report.added_organisations.push(theirs),
+                Some(ours) if *ours == theirs => {}
+                Some(ours) => report.organisation_conflicts.push(OrganisationConflict {
+                    ours: ours.clone(),
+                    theirs,
+                }),
+            }
+        }
+
+        report
+    }
+
+    /// Merges `other` database into this one, see `MergeReport` for details.
+    pub fn merge(&mut self, other: &Locations, strategy: MergeStrategy) -> MergeReport {
+        let report = self.plan_merge(other, strategy);
+
+        for location in report.added.iter().chain(report.updated.iter()) {
+            self.restore(location.clone());
+        }
+        if strategy == MergeStrategy::TakeTheirs {
+            // Incoming version is kept with its audit metadata,
+            // but must not be older than ours, so that `restore` accepts it
+            for conflict in report.conflicts.iter() {
+                self.restore(conflict.theirs.clone().rebased_onto(&conflict.ours));
+            }
+        }
+        for organisation in report.added_organisations.iter() {
+            self.organisations
+                .insert(organisation.get_id(), organisation.clone());
+        }
+        for conflict in report.organisation_conflicts.iter() {
+            let organisation = conflict.resolve(strategy);
+            self.organisations
+                .insert(organisation.get_id(), organisation.clone());
+        }
+
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn renamed(location: &Location, name: &str) -> Location {
+        let mut location = location.clone();
+        location.name = name.into();
+        location
+    }
+
+    #[test]
+    fn merge_adds_new_and_newer_locations() {
+        let mut ours = Locations::new();
+        let shared = ours.push_new(|loc| loc.name = "Shared".into());
+        let mut theirs = ours.clone();
+        let updated = theirs.push_update(renamed(&shared, "Updated")).unwrap();
+        let added = theirs.push_new(|loc| loc.name = "Added".into());
+
+        let report = ours.merge(&theirs, MergeStrategy::KeepOurs);
+
+        assert_eq!(report.added, vec![added]);
+        assert_eq!(report.updated, vec![updated]);
+        assert!(report.conflicts.is_empty());
+        assert_eq!(ours, theirs);
+    }
+
+    #[test]
+    fn merge_detects_locations_changed_on_both_sides() {
+        let mut ours = Locations::new();
+        let shared = ours.push_new(|loc| loc.name = "Shared".into());
+        let mut theirs = ours.clone();
+        let our_version = ours.push_update(renamed(&shared, "Ours")).unwrap();
+        let their_version = theirs.push_update(renamed(&shared, "Theirs")).unwrap();
+
+        let preview = ours.plan_merge(&theirs, MergeStrategy::KeepOurs);
+        assert_eq!(preview.conflicts.len(), 1);
+        assert!(preview.is_noop());
+
+        ours.merge(&theirs, MergeStrategy::TakeTheirs);
+        let merged = &ours.locations[&shared.get_id()];
+        assert_eq!(merged.name, their_version.name);
+        assert_eq!(merged.updated_at, their_version.updated_at);
+        assert_eq!(merged.updated_by, their_version.updated_by);
+        assert!(merged.get_revision() >= our_version.get_revision());
+        assert!(merged.descends_from(&our_version));
+        assert!(merged.descends_from(&their_version));
+    }
+
+    #[test]
+    fn merge_detects_conflicts_regardless_of_revision_counters() {
+        let mut ours = Locations::new();
+        let shared = ours.push_new(|loc| loc.name = "Shared".into());
+        let mut theirs = ours.clone();
+        let our_version = ours.push_update(renamed(&shared, "Ours")).unwrap();
+        ours.push_update(renamed(&our_version, "Ours again"))
+            .unwrap();
+        theirs.push_update(renamed(&shared, "Theirs")).unwrap();
+
+        let report = ours.plan_merge(&theirs, MergeStrategy::KeepOurs);
+        assert_eq!(report.conflicts.len(), 1);
+        assert!(report.outdated.is_empty());
+
+        let report = theirs.plan_merge(&ours, MergeStrategy::KeepOurs);
+        assert_eq!(report.conflicts.len(), 1);
+        assert!(report.updated.is_empty());
+    }
+
+    #[test]
+    fn merge_takes_versions_derived_from_ours() {
+        let mut ours = Locations::new();
+        let shared = ours.push_new(|loc| loc.name = "Shared".into());
+        let mut theirs = ours.clone();
+        let their_version = theirs.push_update(renamed(&shared, "Theirs")).unwrap();
+
+        let report = ours.plan_merge(&theirs, MergeStrategy::KeepOurs);
+        assert_eq!(report.updated, vec![their_version.clone()]);
+        let report = theirs.plan_merge(&ours, MergeStrategy::KeepOurs);
+        assert_eq!(report.outdated, vec![shared]);
+    }
+
+    #[test]
+    fn merge_detects_organisations_changed_on_both_sides() {
+        let mut ours = Locations::new();
+        let mut organisation = Organisation::default();
+        organisation.name = "Fundacja".into();
+        ours.push_organisation(organisation.clone());
+        let mut theirs = ours.clone();
+        organisation.name = "Fundacja Sopocka".into();
+        theirs.push_organisation(organisation.clone());
+        let added = Organisation::default();
+        theirs.push_organisation(added.clone());
+
+        let report = ours.plan_merge(&theirs, MergeStrategy::KeepOurs);
+        assert_eq!(report.added_organisations, vec![added]);
+        assert_eq!(report.organisation_conflicts.len(), 1);
+        assert!(!report.is_noop());
+
+        let mut merged = ours.clone();
+        merged.merge(&theirs, MergeStrategy::KeepOurs);
+        assert_eq!(
+            merged.organisations[&organisation.get_id()]
+                .name
+                .default_text(),
+            "Fundacja"
+        );
+        ours.merge(&theirs, MergeStrategy::TakeTheirs);
+        assert_eq!(ours.organisations, theirs.organisations);
+
+        let report = ours.plan_merge(&theirs, MergeStrategy::TakeTheirs);
+        assert!(report.is_noop());
+    }
+}
@@ -0,0 +1,585 @@
+/*
+Copyright (C) 2025 Mateusz Mazur (Mazurel) <mateusz.mazur@e.email>
+
+This program is free software; you can redistribute it and/or
+modify it under the terms of the GNU General Public License
+as published by the Free Software Foundation; either version 2
+of the License, or (at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program; if not, see
+<https://www.gnu.org/licenses/>.
+*/
+
+//! Schema versioning of the serialized `Locations` database.
+//!
+//! Every database serialized with `Locations::to_bin_data` stores its
+//! schema version under `SCHEMA_VERSION_KEY`. Files written before the
+//! versioning was introduced do not have this key and are treated as
+//! version 1.
+//!
+//! Migrations work on raw BSON documents, so that they do not depend
+//! on the current shape of the Rust types. When changing the format:
+//! 1. Bump `CURRENT_SCHEMA_VERSION`.
+//! 2. Write `migrate_vN_to_vN+1` that only uses `bson` primitives.
+//! 3. Register it in `MIGRATIONS`.
+
+use crate::{
+    error::{Error, Result},
+    localized::DEFAULT_CONTENT_LOCALE,
+};
+use bson::{doc, Bson, Document};
+
+pub type SchemaVersion = u32;
+
+/// Version of the schema written by this version of the library.
+pub const CURRENT_SCHEMA_VERSION: SchemaVersion = 13;
+
+/// Version assumed for documents that do not store it explicitly.
+pub const INITIAL_SCHEMA_VERSION: SchemaVersion = 1;
+
+pub const SCHEMA_VERSION_KEY: &str = "schema_version";
+
+const LOCATIONS_KEY: &str = "locations";
+
+pub struct Migration {
+    /// Migration takes document in this version
+    /// and leaves it in the `from_version + 1` version.
+    pub from_version: SchemaVersion,
+    pub migrate: fn(&mut Document) -> Result<()>,
+}
+
+/// All registered migrations, must be kept in ascending order.
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        from_version: 1,
+        migrate: migrate_v1_to_v2,
+    },
+    Migration {
+        from_version: 2,
+        migrate: migrate_v2_to_v3,
+    },
+    Migration {
+        from_version: 3,
+        migrate: migrate_v3_to_v4,
+    },
+    Migration {
+        from_version: 4,
+        migrate: migrate_v4_to_v5,
+    },
+    Migration {
+        from_version: 5,
+        migrate: migrate_v5_to_v6,
+    },
+    Migration {
+        from_version: 6,
+        migrate: migrate_v6_to_v7,
+    },
+    Migration {
+        from_version: 7,
+        migrate: migrate_v7_to_v8,
+    },
+    Migration {
+        from_version: 8,
+        migrate: migrate_v8_to_v9,
+    },
+    Migration {
+        from_version: 9,
+        migrate: migrate_v9_to_v10,
+    },
+    Migration {
+        from_version: 10,
+        migrate: migrate_v10_to_v11,
+    },
+    Migration {
+        from_version: 11,
+        migrate: migrate_v11_to_v12,
+    },
+    Migration {
+        from_version: 12,
+        migrate: migrate_v12_to_v13,
+    },
+];
+
+/// Returns schema version stored in the `document`.
+pub fn schema_version_of(document: &Document) -> Result<SchemaVersion> {
+    let invalid_version = |version: &dyn std::fmt::Display| {
+        Error::CorruptData(format!("Invalid schema version: {version}"))
+    };
+    match document.get(SCHEMA_VERSION_KEY) {
+        None => Ok(INITIAL_SCHEMA_VERSION),
+        Some(Bson::Int32(version)) => {
+            SchemaVersion::try_from(*version).map_err(|_| invalid_version(version))
+        }
+        Some(Bson::Int64(version)) => {
+            SchemaVersion::try_from(*version).map_err(|_| invalid_version(version))
+        }
+        Some(other) => Err(invalid_version(other)),
+    }
+}
+
+pub fn set_schema_version(document: &mut Document, version: SchemaVersion) {
+    document.insert(SCHEMA_VERSION_KEY, version as i64);
+}
+
+/// Runs all migrations needed to bring `document` to the `CURRENT_SCHEMA_VERSION`.
+pub fn migrate_to_current_version(document: &mut Document) -> Result<()> {
+    let mut version = schema_version_of(document)?;
+
+    if version > CURRENT_SCHEMA_VERSION {
+        return Err(Error::UnsupportedVersion {
+            version,
+            supported_version: CURRENT_SCHEMA_VERSION,
+        });
+    }
+
+    while version < CURRENT_SCHEMA_VERSION {
+        let migration = MIGRATIONS
+            .iter()
+            .find(|migration| migration.from_version == version)
+            .ok_or_else(|| {
+                Error::CorruptData(format!("Missing migration from schema version {version}"))
+            })?;
+        (migration.migrate)(document)?;
+        version += 1;
+        set_schema_version(document, version);
+    }
+
+    Ok(())
+}
+
+/// Calls `modify_fn` on every serialized location in the database `document`.
+fn for_each_location<F>(document: &mut Document, mut modify_fn: F) -> Result<()>
+where
+    F: FnMut(&mut Document) -> Result<()>,
+{
+    let locations = match document.get_mut(LOCATIONS_KEY) {
+        Some(Bson::Document(locations)) => locations,
+        Some(other) => {
+            return Err(Error::CorruptData(format!(
+                "Invalid locations entry: {other}"
+            )))
+        }
+        None => return Ok(()),
+    };
+
+    for (id, location) in locations.iter_mut() {
+        match location {
+            Bson::Document(location) => modify_fn(location)?,
+            other => {
+                return Err(Error::CorruptData(format!(
+                    "Invalid location {id}: {other}"
+                )))
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Version 1 relied on `#[serde(default)]` for fields added later on,
+/// version 2 stores all of them explicitly.
+fn migrate_v1_to_v2(document: &mut Document) -> Result<()> {
+    for_each_location(document, |location| {
+        if !location.contains_key("description") {
+            location.insert("description", "");
+        }
+        if !location.contains_key("contact_methods") {
+            location.insert("contact_methods", doc! { "methods": [] });
+        }
+        if !location.contains_key("opened_hours") {
+            location.insert("opened_hours", doc! { "time_span_per_day": {} });
+        }
+        Ok(())
+    })
+}
+
+/// Version 3 introduced per-location revisions.
+fn migrate_v2_to_v3(document: &mut Document) -> Result<()> {
+    for_each_location(document, |location| {
+        if !location.contains_key("revision") {
+            location.insert("revision", 0i64);
+        }
+        Ok(())
+    })
+}
+
+/// Version 4 introduced optional coordinates of locations.
+fn migrate_v3_to_v4(document: &mut Document) -> Result<()> {
+    for_each_location(document, |location| {
+        if !location.contains_key("coordinates") {
+            location.insert("coordinates", Bson::Null);
+        }
+        Ok(())
+    })
+}
+
+/// Version 5 introduced structured addresses, free-form ones are parsed
+/// with the frozen copy of the parser, see `v5_address`.
+fn migrate_v4_to_v5(document: &mut Document) -> Result<()> {
+    for_each_location(document, |location| {
+        if let Ok(text) = location.get_str("address") {
+            let address = v5_address::from_free_form(text);
+            location.insert("address", address);
+        }
+        Ok(())
+    })
+}
+
+/// Copy of the free-form address parser as it was when version 5 was released.
+/// It must not change, so that migrated documents do not depend on the library version.
+mod v5_address {
+    use bson::{doc, Document};
+
+    const UNIT_PREFIXES: &[&str] = &["lok.", "m."];
+    const VOIVODESHIP_PREFIXES: &[&str] = &["woj.", "województwo"];
+
+    #[derive(Default)]
+    struct Address {
+        street: String,
+        building: String,
+        unit: String,
+        postal_code: String,
+        city: String,
+        voivodeship: String,
+        remarks: String,
+        needs_review: bool,
+    }
+
+    fn is_postal_code(text: &str) -> bool {
+        let bytes = text.as_bytes();
+        bytes.len() == 6
+            && bytes[2] == b'-'
+            && bytes
+                .iter()
+                .enumerate()
+                .all(|(i, b)| i == 2 || b.is_ascii_digit())
+    }
+
+    fn split_postal_code(part: &str) -> Option<(String, &str)> {
+        let (region, rest) = part.split_at_checked(2)?;
+        let rest = rest.trim_start().strip_prefix(['-', '–'])?.trim_start();
+        let (office, city) = rest.split_at_checked(3)?;
+        let postal_code = format!("{region}-{office}");
+        let is_separated = city.is_empty() || city.starts_with(char::is_whitespace);
+        (is_postal_code(&postal_code) && is_separated).then(|| (postal_code, city.trim()))
+    }
+
+    fn is_building_number(text: &str) -> bool {
+        text.starts_with(|c: char| c.is_ascii_digit())
+            && text
+                .chars()
+                .all(|c| c.is_alphanumeric() || c == '/' || c == '-')
+    }
+
+    fn is_building_letter(text: &str) -> bool {
+        let mut chars = text.chars();
+        chars.next().is_some_and(char::is_alphabetic) && chars.next().is_none()
+    }
+
+    fn strip_any_prefix<'a>(text: &'a str, prefixes: &[&str]) -> Option<&'a str> {
+        prefixes.iter().find_map(|prefix| {
+            let (head, rest) = (text.get(..prefix.len())?, text.get(prefix.len()..)?);
+            let rest = rest.trim();
+            (head.to_lowercase() == *prefix && !rest.is_empty()).then_some(rest)
+        })
+    }
+
+    fn parse_recognised(text: &str) -> Address {
+        let mut address = Address::default();
+        let mut remarks = vec![];
+        for part in text.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            if address.postal_code.is_empty() {
+                if let Some((postal_code, city)) = split_postal_code(part) {
+                    address.postal_code = postal_code;
+                    if !city.is_empty() {
+                        let previous_city = std::mem::replace(&mut address.city, city.to_string());
+                        if !previous_city.is_empty() {
+                            remarks.push(previous_city);
+                        }
+                    }
+                    continue;
+                }
+            }
+            if address.voivodeship.is_empty() {
+                if let Some(voivodeship) = strip_any_prefix(part, VOIVODESHIP_PREFIXES) {
+                    address.voivodeship = voivodeship.to_string();
+                    continue;
+                }
+            }
+            if address.building.is_empty() {
+                if let Some(city) = parse_street(&mut address, part) {
+                    match (city.is_empty(), address.city.is_empty()) {
+                        (true, _) => {}
+                        (false, true) => address.city = city,
+                        (false, false) => remarks.push(city),
+                    }
+                    continue;
+                }
+            }
+            if address.unit.is_empty() && !address.building.is_empty() {
+                if let Some(unit) = strip_any_prefix(part, UNIT_PREFIXES) {
+                    address.unit = unit.to_string();
+                    continue;
+                }
+            }
+            remarks.push(part.to_string());
+        }
+        address.remarks = remarks.join(", ");
+        address
+    }
+
+    fn parse_street(address: &mut Address, part: &str) -> Option<String> {
+        let mut words: Vec<&str> = part.split_whitespace().collect();
+        let mut unit = "";
+        if let [.., prefix, unit_number] = words[..] {
+            if UNIT_PREFIXES.contains(&prefix.to_lowercase().as_str()) {
+                unit = unit_number;
+                words.truncate(words.len() - 2);
+            }
+        }
+        let building_idx = (1..words.len())
+            .rev()
+            .find(|idx| is_building_number(words[*idx]))?;
+
+        let (building, building_unit) = match words[building_idx].split_once('/') {
+            Some((building, unit)) => (building, unit),
+            None => (words[building_idx], ""),
+        };
+        let mut building = building.to_string();
+        let mut city_idx = building_idx + 1;
+        if let Some(letter) = words.get(city_idx).filter(|word| is_building_letter(word)) {
+            building.push_str(letter);
+            city_idx += 1;
+        }
+        address.street = words[..building_idx].join(" ");
+        address.building = building;
+        address.unit = match building_unit.is_empty() {
+            true => unit.to_string(),
+            false => building_unit.to_string(),
+        };
+        Some(words[city_idx..].join(" "))
+    }
+
+    /// Structured address document of the free-form `text`.
+    pub(super) fn from_free_form(text: &str) -> Document {
+        let mut address = parse_recognised(text);
+        let is_empty = [
+            &address.street,
+            &address.building,
+            &address.unit,
+            &address.postal_code,
+            &address.city,
+            &address.voivodeship,
+            &address.remarks,
+        ]
+        .iter()
+        .all(|field| field.trim().is_empty());
+        let is_complete = !address.building.is_empty() && !address.postal_code.is_empty();
+        address.needs_review = !is_empty && !is_complete;
+        doc! {
+            "street": address.street,
+            "building": address.building,
+            "unit": address.unit,
+            "postal_code": address.postal_code,
+            "city": address.city,
+            "voivodeship": address.voivodeship,
+            "remarks": address.remarks,
+            "needs_review": address.needs_review,
+        }
+    }
+}
+
+/// Version 6 introduced audit metadata, unknown for the existing locations.
+fn migrate_v5_to_v6(document: &mut Document) -> Result<()> {
+    for_each_location(document, |location| {
+        for key in ["created_at", "updated_at", "last_verified_at"] {
+            if !location.contains_key(key) {
+                location.insert(key, Bson::Null);
+            }
+        }
+        if !location.contains_key("updated_by") {
+            location.insert("updated_by", "");
+        }
+        Ok(())
+    })
+}
+
+/// Version 7 introduced publication workflow,
+/// existing locations were already visible, so they are published.
+fn migrate_v6_to_v7(document: &mut Document) -> Result<()> {
+    for_each_location(document, |location| {
+        if !location.contains_key("publication_state") {
+            location.insert("publication_state", "Published");
+        }
+        Ok(())
+    })
+}
+
+/// Version 8 introduced soft deletion, see `trash`.
+fn migrate_v7_to_v8(document: &mut Document) -> Result<()> {
+    for_each_location(document, |location| {
+        if !location.contains_key("deleted_at") {
+            location.insert("deleted_at", Bson::Null);
+        }
+        Ok(())
+    })
+}
+
+/// Version 9 introduced localized names and descriptions,
+/// existing ones are written in the `DEFAULT_CONTENT_LOCALE`.
+fn migrate_v8_to_v9(document: &mut Document) -> Result<()> {
+    for_each_location(document, |location| {
+        for key in ["name", "description"] {
+            if let Ok(text) = location.get_str(key) {
+                let localized = match text.trim().is_empty() {
+                    true => doc! {},
+                    false => doc! { DEFAULT_CONTENT_LOCALE: text },
+                };
+                location.insert(key, localized);
+            }
+        }
+        Ok(())
+    })
+}
+
+/// Version 10 introduced accessibility, unknown for the existing locations.
+fn migrate_v9_to_v10(document: &mut Document) -> Result<()> {
+    for_each_location(document, |location| {
+        if !location.contains_key("accessibility") {
+            location.insert("accessibility", doc! {});
+        }
+        Ok(())
+    })
+}
+
+/// Version 11 introduced service terms, unknown for the existing locations.
+fn migrate_v10_to_v11(document: &mut Document) -> Result<()> {
+    for_each_location(document, |location| {
+        if !location.contains_key("service_terms") {
+            location.insert("service_terms", doc! {});
+        }
+        Ok(())
+    })
+}
+
+/// Version 12 introduced organisations, existing locations are not branches of any.
+fn migrate_v11_to_v12(document: &mut Document) -> Result<()> {
+    if !document.contains_key("organisations") {
+        document.insert("organisations", doc! {});
+    }
+    for_each_location(document, |location| {
+        if !location.contains_key("organisation") {
+            location.insert("organisation", Bson::Null);
+        }
+        Ok(())
+    })
+}
+
+/// Version 13 introduced revision IDs, existing locations have no known history.
+fn migrate_v12_to_v13(document: &mut Document) -> Result<()> {
+    for_each_location(document, |location| {
+        if !location.contains_key("revision_ids") {
+            location.insert("revision_ids", Bson::Array(Vec::new()));
+        }
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn document_without_version_is_migrated_to_current_version() {
+        let mut document = doc! {
+            "locations": {
+                "some-id": {
+                    "name": "Example",
+                    "address": "ul. Długa 5, 80-001 Gdańsk",
+                    "tags": { "tags": [] },
+                }
+            }
+        };
+        assert_eq!(schema_version_of(&document), Ok(INITIAL_SCHEMA_VERSION));
+
+        migrate_to_current_version(&mut document).expect("Migration should succeed");
+
+        assert_eq!(schema_version_of(&document), Ok(CURRENT_SCHEMA_VERSION));
+        let location = document
+            .get_document("locations")
+            .and_then(|locations| locations.get_document("some-id"))
+            .unwrap();
+        assert_eq!(location.get_document("description").unwrap(), &doc! {});
+        assert_eq!(
+            location.get_document("name").unwrap(),
+            &doc! { "pl": "Example" }
+        );
+        assert!(location.contains_key("contact_methods"));
+        assert!(location.contains_key("opened_hours"));
+        assert_eq!(location.get_i64("revision").unwrap(), 0);
+        assert_eq!(location.get("coordinates"), Some(&Bson::Null));
+        assert_eq!(location.get_document("accessibility").unwrap(), &doc! {});
+        assert_eq!(location.get_document("service_terms").unwrap(), &doc! {});
+        assert_eq!(location.get("organisation"), Some(&Bson::Null));
+        assert_eq!(document.get_document("organisations").unwrap(), &doc! {});
+        let address = location.get_document("address").unwrap();
+        assert_eq!(address.get_str("postal_code").unwrap(), "80-001");
+        assert!(!address.get_bool("needs_review").unwrap());
+        assert_eq!(location.get("last_verified_at"), Some(&Bson::Null));
+        assert_eq!(location.get_str("updated_by").unwrap(), "");
+        assert_eq!(location.get_str("publication_state").unwrap(), "Published");
+        assert_eq!(location.get("deleted_at"), Some(&Bson::Null));
+    }
+
+    #[test]
+    fn free_form_address_is_structured() {
+        let mut document = doc! {
+            "locations": {
+                "some-id": {
+                    "name": "Example",
+                    "address": "ul. Leczkowa 1 A, 80 – 432 Gdańsk",
+                    "tags": { "tags": [] },
+                }
+            }
+        };
+        migrate_to_current_version(&mut document).expect("Migration should succeed");
+
+        let address = document
+            .get_document("locations")
+            .and_then(|locations| locations.get_document("some-id"))
+            .and_then(|location| location.get_document("address"))
+            .unwrap();
+        assert_eq!(address.get_str("street").unwrap(), "ul. Leczkowa");
+        assert_eq!(address.get_str("building").unwrap(), "1A");
+        assert_eq!(address.get_str("postal_code").unwrap(), "80-432");
+        assert_eq!(address.get_str("city").unwrap(), "Gdańsk");
+        assert_eq!(address.get_str("remarks").unwrap(), "");
+        assert!(!address.get_bool("needs_review").unwrap());
+    }
+
+    #[test]
+    fn newer_schema_version_is_rejected() {
+        let mut document = doc! { "locations": {} };
+        set_schema_version(&mut document, CURRENT_SCHEMA_VERSION + 1);
+        assert_eq!(
+            migrate_to_current_version(&mut document),
+            Err(Error::UnsupportedVersion {
+                version: CURRENT_SCHEMA_VERSION + 1,
+                supported_version: CURRENT_SCHEMA_VERSION,
+            })
+        );
+    }
+
+    #[test]
+    fn migrations_are_registered_for_every_version() {
+        for version in INITIAL_SCHEMA_VERSION..CURRENT_SCHEMA_VERSION {
+            assert!(MIGRATIONS.iter().any(|m| m.from_version == version));
+        }
+    }
+}
@@ -0,0 +1,254 @@
+/*
+Copyright (C) 2025 Mateusz Mazur (Mazurel) <mateusz.mazur@e.email>
+
+This program is free software; you can redistribute it and/or
+modify it under the terms of the GNU General Public License
+as published by the Free Software Foundation; either version 2
+of the License, or (at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program; if not, see
+<https://www.gnu.org/licenses/>.
+*/
+
+//! Organisations running many branches, e.g. NGOs or municipal social-welfare centres (MOPS).
+//!
+//! Branches are ordinary locations linked to the organisation,
+//! fields left empty in a branch are inherited from its organisation.
+
+use crate::{
+    contact::ContactMethods,
+    error::{Error, Result},
+    localized::LocalizedText,
+    locations::{Location, Locations},
+    tags::Tags,
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Organisation {
+    id: Uuid,
+    #[serde(default)]
+    pub name: LocalizedText,
+    #[serde(default)]
+    pub description: LocalizedText,
+    #[serde(default)]
+    pub contact_methods: ContactMethods,
+    #[serde(default)]
+    pub tags: Tags,
+}
+
+impl Organisation {
+    pub fn get_id(&self) -> Uuid {
+        self.id
+    }
+}
+
+impl Default for Organisation {
+    fn default() -> Self {
+        Organisation {
+            id: Uuid::new_v4(),
+            name: LocalizedText::new(),
+            description: LocalizedText::new(),
+            contact_methods: ContactMethods::default(),
+            tags: Tags::new(),
+        }
+    }
+}
+
+impl Location {
+    /// Fills the fields not overridden by the branch with the ones of `organisation`.
+    /// Tags are overridden per tag group, so a branch tagged with its city
+    /// still inherits e.g. the topics of the organisation.
+    fn inherit_from(&mut self, organisation: &Organisation) {
+        if self.description.is_empty() {
+            self.description = organisation.description.clone();
+        }
+        if self.contact_methods.all_contact_methods().is_empty() {
+            self.contact_methods = organisation.contact_methods.clone();
+        }
+        let overridden_groups: Vec<_> = self
+            .tags
+            .get_all_tags()
+            .into_iter()
+            .map(|tag| tag.get_associated_group())
+            .collect();
+        for tag in organisation.tags.get_all_tags() {
+            if !overridden_groups.contains(&tag.get_associated_group()) {
+                self.tags.define_tag(&tag.name);
+            }
+        }
+    }
+}
+
+impl Locations {
+    pub fn organisation_of(&self, location: &Location) -> Option<&Organisation> {
+        self.organisations.get(&location.organisation?)
+    }
+
+    /// `location` with the fields inherited from its organisation.
+    pub fn with_inherited_fields(&self, location: &Location) -> Location {
+        let mut location = location.clone();
+        if let Some(organisation) = self.organisation_of(&location) {
+            location.inherit_from(organisation);
+        }
+        location
+    }
+
+    /// Database in which all branches have the inherited fields filled in,
+    /// meant for searching and displaying, not for editing.
+    pub fn with_inheritance(&self) -> Locations {
+        Locations {
+            locations: self
+                .locations
+                .iter()
+                .map(|(id, location)| (*id, self.with_inherited_fields(location)))
+                .collect(),
+            organisations: self.organisations.clone(),
+        }
+    }
+
+    /// Organisations sorted by name.
+    pub fn organisations_in_order(&self) -> Vec<Organisation> {
+        let mut organisations: Vec<Organisation> = self.organisations.values().cloned().collect();
+        organisations.sort_by(|o1, o2| o1.name.default_text().cmp(o2.name.default_text()));
+        organisations
+    }
+
+    /// Branches of the organisation which are not deleted, sorted by name.
+    pub fn branches_of(&self, organisation_id: Uuid) -> Vec<Location> {
+        let mut branches: Vec<Location> = self
+            .locations
+            .values()
+            .filter(|location| {
+                location.organisation == Some(organisation_id) && !location.is_deleted()
+            })
+            .cloned()
+            .collect();
+        branches.sort_by(|l1, l2| l1.name.default_text().cmp(l2.name.default_text()));
+        branches
+    }
+
+    /// Other branches of the organisation of `location`, empty if it has none.
+    pub fn other_branches(&self, location: &Location) -> Vec<Location> {
+        match location.organisation {
+            Some(organisation_id) => self
+                .branches_of(organisation_id)
+                .into_iter()
+                .filter(|branch| branch.get_id() != location.get_id())
+                .collect(),
+            None => vec![],
+        }
+    }
+
+    /// Adds new organisation or replaces the one with the same ID,
+    /// returning the replaced one.
+    pub fn push_organisation(&mut self, organisation: Organisation) -> Option<Organisation> {
+        self.organisations
+            .insert(organisation.get_id(), organisation)
+    }
+
+    /// Removes the organisation, organisations with branches cannot be removed.
+    /// Branches in the trash count too, as they could be restored from it.
+    pub fn remove_organisation(&mut self, organisation_id: Uuid) -> Result<Organisation> {
+        let organisation = self
+            .organisations
+            .get(&organisation_id)
+            .ok_or(Error::MissingOrganisation(organisation_id))?;
+        let has_branches = self
+            .locations
+            .values()
+            .any(|location| location.organisation == Some(organisation_id));
+        if has_branches {
+            return Err(Error::OrganisationHasBranches(
+                organisation.name.to_string(),
+            ));
+        }
+        Ok(self.organisations.remove(&organisation_id).unwrap())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contact::{Contact, ContactType};
+
+    fn organisation_with_branches() -> (Locations, Organisation, Location, Location) {
+        let mut locations = Locations::new();
+        let mut organisation = Organisation {
+            name: "Fundacja".into(),
+            description: "Pomagamy od 1990 roku".into(),
+            ..Default::default()
+        };
+        organisation.tags.define_tag("LGBT");
+        organisation.tags.define_tag("Pomorskie");
+        organisation
+            .contact_methods
+            .add_new_contact_method(Contact {
+                contact_type: ContactType::PhoneNumber,
+                value: "+48 58 123 45 67".to_string(),
+            });
+        locations.push_organisation(organisation.clone());
+
+        let gdansk = locations.push_new(|loc| {
+            loc.name = "Fundacja Gdańsk".into();
+            loc.organisation = Some(organisation.get_id());
+            loc.tags.define_tag("Gdańsk");
+        });
+        let sopot = locations.push_new(|loc| {
+            loc.name = "Fundacja Sopot".into();
+            loc.description = "Tylko dyżury".into();
+            loc.organisation = Some(organisation.get_id());
+        });
+        (locations, organisation, gdansk, sopot)
+    }
+
+    #[test]
+    fn branches_inherit_fields_they_do_not_override() {
+        let (locations, _, gdansk, sopot) = organisation_with_branches();
+
+        let gdansk = locations.with_inherited_fields(&gdansk);
+        assert_eq!(gdansk.description.default_text(), "Pomagamy od 1990 roku");
+        assert_eq!(gdansk.contact_methods.all_contact_methods().len(), 1);
+        assert!(gdansk.tags.has_tag(&"LGBT".parse().unwrap()));
+        assert!(gdansk.tags.has_tag(&"Gdańsk".parse().unwrap()));
+        // City of the branch overrides the region of the organisation
+        assert!(!gdansk.tags.has_tag(&"Pomorskie".parse().unwrap()));
+
+        let sopot = locations.with_inherited_fields(&sopot);
+        assert_eq!(sopot.description.default_text(), "Tylko dyżury");
+        assert!(sopot.tags.has_tag(&"Pomorskie".parse().unwrap()));
+    }
+
+    #[test]
+    fn organisation_lists_other_branches_and_cannot_be_removed_with_them() {
+        let (mut locations, organisation, gdansk, sopot) = organisation_with_branches();
+
+        assert_eq!(locations.other_branches(&gdansk), vec![sopot.clone()]);
+        assert!(matches!(
+            locations.remove_organisation(organisation.get_id()),
+            Err(Error::OrganisationHasBranches(_))
+        ));
+
+        let gdansk = locations.move_to_trash(&gdansk).unwrap();
+        locations.move_to_trash(&sopot).unwrap();
+        assert!(locations.branches_of(organisation.get_id()).is_empty());
+        assert!(matches!(
+            locations.remove_organisation(organisation.get_id()),
+            Err(Error::OrganisationHasBranches(_))
+        ));
+
+        locations.remove(gdansk).unwrap();
+        let mut sopot = locations.locations[&sopot.get_id()].clone();
+        sopot.organisation = None;
+        locations.push_update(sopot).unwrap();
+        assert!(locations.remove_organisation(organisation.get_id()).is_ok());
+        assert!(locations.organisations.is_empty());
+    }
+}
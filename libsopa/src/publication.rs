@@ -0,0 +1,177 @@
+/*
+Copyright (C) 2025 Mateusz Mazur (Mazurel) <mateusz.mazur@e.email>
+
+This program is free software; you can redistribute it and/or
+modify it under the terms of the GNU General Public License
+as published by the Free Software Foundation; either version 2
+of the License, or (at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program; if not, see
+<https://www.gnu.org/licenses/>.
+*/
+
+//! Publication workflow, only published locations are shown to the people seeking help.
+
+use crate::{
+    error::{Error, Result},
+    locations::{Location, Locations},
+    validation::ValidationIssue,
+};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PublicationState {
+    /// Being written, new locations start in this state.
+    Draft,
+    /// Waiting for another editor to check it.
+    InReview,
+    Published,
+    /// No longer shown, e.g. the location was closed.
+    Archived,
+}
+
+impl PublicationState {
+    pub const ALL: [PublicationState; 4] = [
+        PublicationState::Draft,
+        PublicationState::InReview,
+        PublicationState::Published,
+        PublicationState::Archived,
+    ];
+
+    /// Locations stored before the workflow existed were all visible.
+    pub fn of_existing_location() -> Self {
+        PublicationState::Published
+    }
+
+    pub fn allowed_transitions(&self) -> &'static [PublicationState] {
+        match self {
+            PublicationState::Draft => &[PublicationState::InReview, PublicationState::Archived],
+            PublicationState::InReview => &[PublicationState::Draft, PublicationState::Published],
+            PublicationState::Published => &[PublicationState::Draft, PublicationState::Archived],
+            PublicationState::Archived => &[PublicationState::Draft],
+        }
+    }
+
+    pub fn can_transition_to(&self, next: PublicationState) -> bool {
+        self.allowed_transitions().contains(&next)
+    }
+}
+
+impl fmt::Display for PublicationState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = match self {
+            PublicationState::Draft => t!("publication-state-draft"),
+            PublicationState::InReview => t!("publication-state-in-review"),
+            PublicationState::Published => t!("publication-state-published"),
+            PublicationState::Archived => t!("publication-state-archived"),
+        };
+        write!(f, "{state}")
+    }
+}
+
+impl Location {
+    pub fn publication_state(&self) -> PublicationState {
+        self.publication_state
+    }
+
+    pub fn is_published(&self) -> bool {
+        self.publication_state == PublicationState::Published
+    }
+
+    /// Moves the location to `next` state, if the transition is allowed.
+    /// Locations with validation errors cannot be published.
+    pub fn transition_to(&mut self, next: PublicationState) -> Result<()> {
+        let from = self.publication_state;
+        if !from.can_transition_to(next) {
+            return Err(Error::InvalidTransition { from, to: next });
+        }
+        if next == PublicationState::Published {
+            let errors: Vec<ValidationIssue> = self
+                .validate()
+                .into_iter()
+                .filter(ValidationIssue::is_error)
+                .collect();
+            if !errors.is_empty() {
+                return Err(Error::ValidationFailed(errors));
+            }
+        }
+        self.publication_state = next;
+        Ok(())
+    }
+}
+
+impl Locations {
+    /// Locations visible in the finder, deleted ones are never visible.
+    pub fn published(&self) -> Locations {
+        Locations {
+            locations: self
+                .locations
+                .iter()
+                .filter(|(_, location)| location.is_published() && !location.is_deleted())
+                .map(|(id, location)| (*id, location.clone()))
+                .collect(),
+            organisations: self.organisations.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{address::Address, validation::IssueKind};
+
+    #[test]
+    fn only_allowed_transitions_are_made() {
+        let mut location = Location::default();
+        location.name = "Example".into();
+        location.address = Address::from_free_form("ul. Długa 5, 80-001 Gdańsk");
+        location.tags.define_tag("Gdańsk");
+        assert_eq!(location.publication_state(), PublicationState::Draft);
+
+        let skip_review = location.transition_to(PublicationState::Published);
+        assert_eq!(
+            skip_review,
+            Err(Error::InvalidTransition {
+                from: PublicationState::Draft,
+                to: PublicationState::Published
+            })
+        );
+        location.transition_to(PublicationState::InReview).unwrap();
+        location.transition_to(PublicationState::Published).unwrap();
+        assert!(location.is_published());
+        location.transition_to(PublicationState::Archived).unwrap();
+        assert!(location.transition_to(PublicationState::InReview).is_err());
+
+        let mut invalid = Location::default();
+        invalid.transition_to(PublicationState::InReview).unwrap();
+        match invalid.transition_to(PublicationState::Published) {
+            Err(Error::ValidationFailed(issues)) => {
+                assert!(issues
+                    .iter()
+                    .any(|issue| issue.kind == IssueKind::EmptyName));
+                assert!(issues.iter().all(ValidationIssue::is_error));
+            }
+            other => panic!("Expected validation errors, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn only_published_locations_are_visible() {
+        let mut locations = Locations::new();
+        let draft = locations.push_new(|loc| loc.name = "Draft".into());
+        let mut published = locations.push_new(|loc| loc.name = "Published".into());
+        published.publication_state = PublicationState::Published;
+        let published = locations.push_update(published).unwrap();
+
+        let visible = locations.published();
+        assert!(visible.contains(&published));
+        assert!(!visible.contains(&draft));
+    }
+}
@@ -0,0 +1,654 @@
+/*
+Copyright (C) 2025 Mateusz Mazur (Mazurel) <mateusz.mazur@e.email>
+
+This program is free software; you can redistribute it and/or
+modify it under the terms of the GNU General Public License
+as published by the Free Software Foundation; either version 2
+of the License, or (at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program; if not, see
+<https://www.gnu.org/licenses/>.
+*/
+
+//! Filter query language, e.g. `tag:LGBT AND tag:Gdańsk AND open:now AND NOT tag:NFZ`.
+//!
+//! Grammar (`AND` binds stronger than `OR`, words next to each other are joined with `AND`):
+//!
+//! ```text
+//! query   := and ("OR" and)*
+//! and     := not ("AND"? not)*
+//! not     := "NOT" not | "(" query ")" | filter
+//! filter  := field ":" value | value
+//! ```
+//!
+//! Values may be quoted: `tag:"domestic abuse"`.
+
+use crate::{
+    accessibility::AccessibilityFeature,
+    audit::{self, Timestamp},
+    locations::Location,
+    search::fold_diacritics,
+    service_terms::{CostKind, ReferralRequirement},
+    time::{Day, TimePoint},
+};
+use std::{fmt, str::FromStr};
+
+/// Moment in which the query is evaluated, needed by `open:now`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct QueryContext {
+    pub now: Option<(Day, TimePoint)>,
+    /// Needed by `unverified:`, `audit::now` is used if missing.
+    pub timestamp: Option<Timestamp>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OpenedAt {
+    Now,
+    Day(Day),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Filter {
+    /// Tag with given name, in any language.
+    Tag(String),
+    Name(String),
+    Address(String),
+    City(String),
+    /// Postal code or its prefix, e.g. `80-` for the Gdańsk area.
+    PostalCode(String),
+    Description(String),
+    /// Any of name, address or description.
+    Text(String),
+    Open(OpenedAt),
+    /// Not verified in the given number of months, or never.
+    Unverified(u32),
+    /// Feature confirmed to be available, e.g. `accessible:step-free`.
+    Accessible(AccessibilityFeature),
+    Cost(CostKind),
+    Referral(ReferralRequirement),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Query {
+    Filter(Filter),
+    Not(Box<Query>),
+    And(Box<Query>, Box<Query>),
+    Or(Box<Query>, Box<Query>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QueryErrorKind {
+    UnexpectedEnd,
+    UnexpectedToken(String),
+    UnknownField(String),
+    InvalidValue(String),
+    UnclosedQuote,
+}
+
+/// Error of parsing the query, `position` is counted in characters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryError {
+    pub position: usize,
+    pub kind: QueryErrorKind,
+}
+
+impl fmt::Display for QueryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match &self.kind {
+            QueryErrorKind::UnexpectedEnd => t!("query-error-unexpected-end"),
+            QueryErrorKind::UnexpectedToken(token) => {
+                t!("query-error-unexpected-token", token = token)
+            }
+            QueryErrorKind::UnknownField(field) => t!("query-error-unknown-field", field = field),
+            QueryErrorKind::InvalidValue(value) => t!("query-error-invalid-value", value = value),
+            QueryErrorKind::UnclosedQuote => t!("query-error-unclosed-quote"),
+        };
+        write!(
+            f,
+            "{} ({})",
+            message,
+            t!("query-error-position", position = self.position + 1)
+        )
+    }
+}
+
+impl std::error::Error for QueryError {}
+
+pub type QueryResult<T> = std::result::Result<T, QueryError>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum TokenKind {
+    LeftParenthesis,
+    RightParenthesis,
+    And,
+    Or,
+    Not,
+    Filter {
+        field: Option<String>,
+        value: String,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Token {
+    position: usize,
+    kind: TokenKind,
+    text: String,
+}
+
+fn is_word_char(c: char) -> bool {
+    !c.is_whitespace() && !matches!(c, '(' | ')' | '"')
+}
+
+struct Lexer {
+    chars: Vec<char>,
+    position: usize,
+}
+
+impl Lexer {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.position).copied()
+    }
+
+    fn read_while<F: Fn(char) -> bool>(&mut self, predicate: F) -> String {
+        let start = self.position;
+        while self.peek().is_some_and(&predicate) {
+            self.position += 1;
+        }
+        self.chars[start..self.position].iter().collect()
+    }
+
+    /// Reads quoted string, starting at the opening quote.
+    fn read_quoted(&mut self) -> QueryResult<String> {
+        let start = self.position;
+        self.position += 1;
+        let value = self.read_while(|c| c != '"');
+        if self.peek().is_none() {
+            return Err(QueryError {
+                position: start,
+                kind: QueryErrorKind::UnclosedQuote,
+            });
+        }
+        self.position += 1;
+        Ok(value)
+    }
+
+    fn tokenize(mut self) -> QueryResult<Vec<Token>> {
+        let mut tokens = vec![];
+        loop {
+            self.read_while(char::is_whitespace);
+            let position = self.position;
+            let kind = match self.peek() {
+                None => break,
+                Some('(') => {
+                    self.position += 1;
+                    TokenKind::LeftParenthesis
+                }
+                Some(')') => {
+                    self.position += 1;
+                    TokenKind::RightParenthesis
+                }
+                Some('"') => TokenKind::Filter {
+                    field: None,
+                    value: self.read_quoted()?,
+                },
+                Some(_) => {
+                    let word = self.read_while(is_word_char);
+                    match word.as_str() {
+                        "AND" => TokenKind::And,
+                        "OR" => TokenKind::Or,
+                        "NOT" => TokenKind::Not,
+                        _ => match word.split_once(':') {
+                            Some((field, value)) => {
+                                let value = match (value.is_empty(), self.peek()) {
+                                    (true, Some('"')) => self.read_quoted()?,
+                                    _ => value.to_string(),
+                                };
+                                TokenKind::Filter {
+                                    field: Some(field.to_string()),
+                                    value,
+                                }
+                            }
+                            None => TokenKind::Filter {
+                                field: None,
+                                value: word,
+                            },
+                        },
+                    }
+                }
+            };
+            let text = self.chars[position..self.position].iter().collect();
+            tokens.push(Token {
+                position,
+                kind,
+                text,
+            });
+        }
+        Ok(tokens)
+    }
+}
+
+fn parse_day(value: &str) -> Option<Day> {
+    let value = fold_diacritics(value);
+    Day::get_all_days_in_week().into_iter().find(|day| {
+        let key = format!("{day:?}").to_lowercase();
+        key == value
+            || rust_i18n::available_locales!()
+                .into_iter()
+                .any(|locale| fold_diacritics(&t!(key.as_str(), locale = locale)) == value)
+    })
+}
+
+fn parse_filter(field: Option<&str>, value: String, position: usize) -> QueryResult<Filter> {
+    let invalid_value = |value: &str| QueryError {
+        position,
+        kind: QueryErrorKind::InvalidValue(value.to_string()),
+    };
+    if value.is_empty() {
+        return Err(invalid_value(&value));
+    }
+
+    Ok(match field {
+        None | Some("text") => Filter::Text(value),
+        Some("tag") => Filter::Tag(value),
+        Some("name") => Filter::Name(value),
+        Some("address") => Filter::Address(value),
+        Some("city") => Filter::City(value),
+        Some("postal") => Filter::PostalCode(value),
+        Some("description") => Filter::Description(value),
+        Some("open") if value == "now" => Filter::Open(OpenedAt::Now),
+        Some("open") => Filter::Open(OpenedAt::Day(
+            parse_day(&value).ok_or_else(|| invalid_value(&value))?,
+        )),
+        Some("unverified") => Filter::Unverified(value.parse().map_err(|_| invalid_value(&value))?),
+        Some("accessible") => Filter::Accessible(
+            AccessibilityFeature::from_key(&value).ok_or_else(|| invalid_value(&value))?,
+        ),
+        Some("cost") => {
+            Filter::Cost(CostKind::from_key(&value).ok_or_else(|| invalid_value(&value))?)
+        }
+        Some("referral") => Filter::Referral(
+            ReferralRequirement::from_key(&value).ok_or_else(|| invalid_value(&value))?,
+        ),
+        Some(field) => {
+            return Err(QueryError {
+                position,
+                kind: QueryErrorKind::UnknownField(field.to_string()),
+            })
+        }
+    })
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+    /// Position of the end of the query, reported when tokens run out.
+    end_position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&TokenKind> {
+        self.tokens.get(self.position).map(|token| &token.kind)
+    }
+
+    fn unexpected(&self) -> QueryError {
+        match self.tokens.get(self.position) {
+            Some(token) => QueryError {
+                position: token.position,
+                kind: QueryErrorKind::UnexpectedToken(token.text.clone()),
+            },
+            None => QueryError {
+                position: self.end_position,
+                kind: QueryErrorKind::UnexpectedEnd,
+            },
+        }
+    }
+
+    fn parse_or(&mut self) -> QueryResult<Query> {
+        let mut query = self.parse_and()?;
+        while self.peek() == Some(&TokenKind::Or) {
+            self.position += 1;
+            query = Query::Or(Box::new(query), Box::new(self.parse_and()?));
+        }
+        Ok(query)
+    }
+
+    fn parse_and(&mut self) -> QueryResult<Query> {
+        let mut query = self.parse_not()?;
+        loop {
+            match self.peek() {
+                Some(TokenKind::And) => self.position += 1,
+                Some(TokenKind::Not | TokenKind::LeftParenthesis | TokenKind::Filter { .. }) => {}
+                _ => break,
+            }
+            query = Query::And(Box::new(query), Box::new(self.parse_not()?));
+        }
+        Ok(query)
+    }
+
+    fn parse_not(&mut self) -> QueryResult<Query> {
+        let token = self.tokens.get(self.position).cloned();
+        let Some(token) = token else {
+            return Err(self.unexpected());
+        };
+        match token.kind {
+            TokenKind::Not => {
+                self.position += 1;
+                Ok(Query::Not(Box::new(self.parse_not()?)))
+            }
+            TokenKind::LeftParenthesis => {
+                self.position += 1;
+                let query = self.parse_or()?;
+                if self.peek() != Some(&TokenKind::RightParenthesis) {
+                    return Err(self.unexpected());
+                }
+                self.position += 1;
+                Ok(query)
+            }
+            TokenKind::Filter { field, value } => {
+                self.position += 1;
+                Ok(Query::Filter(parse_filter(
+                    field.as_deref(),
+                    value,
+                    token.position,
+                )?))
+            }
+            _ => Err(self.unexpected()),
+        }
+    }
+}
+
+impl FromStr for Query {
+    type Err = QueryError;
+
+    fn from_str(query: &str) -> QueryResult<Self> {
+        let lexer = Lexer {
+            chars: query.chars().collect(),
+            position: 0,
+        };
+        let mut parser = Parser {
+            tokens: lexer.tokenize()?,
+            position: 0,
+            end_position: query.chars().count(),
+        };
+        let query = parser.parse_or()?;
+        match parser.position == parser.tokens.len() {
+            true => Ok(query),
+            false => Err(parser.unexpected()),
+        }
+    }
+}
+
+fn contains_folded(text: &str, value: &str) -> bool {
+    fold_diacritics(text).contains(&fold_diacritics(value))
+}
+
+impl Filter {
+    pub fn matches(&self, location: &Location, context: &QueryContext) -> bool {
+        match self {
+            Filter::Tag(name) => {
+                let name = fold_diacritics(name);
+                location.tags.get_all_tags().into_iter().any(|tag| {
+                    fold_diacritics(&tag.name) == name
+                        || tag
+                            .human_readable_in_every_locale()
+                            .iter()
+                            .any(|human_readable| fold_diacritics(human_readable) == name)
+                })
+            }
+            Filter::Name(value) => location
+                .name
+                .texts()
+                .into_iter()
+                .any(|name| contains_folded(name, value)),
+            Filter::Address(value) => contains_folded(&location.address.to_string(), value),
+            Filter::City(value) => {
+                fold_diacritics(&location.address.city) == fold_diacritics(value)
+            }
+            Filter::PostalCode(value) => location.address.postal_code.starts_with(value.as_str()),
+            Filter::Description(value) => location
+                .description
+                .texts()
+                .into_iter()
+                .any(|description| contains_folded(description, value)),
+            Filter::Text(value) => {
+                let address = location.address.to_string();
+                let mut texts = location.name.texts();
+                texts.push(&address);
+                texts.extend(location.description.texts());
+                texts.into_iter().any(|text| contains_folded(text, value))
+            }
+            Filter::Open(OpenedAt::Day(day)) => location.opened_hours.is_opened_on_day(day),
+            Filter::Open(OpenedAt::Now) => match &context.now {
+                Some((day, time)) => location.opened_hours.is_opened_at(day, time),
+                None => false,
+            },
+            Filter::Unverified(months) => {
+                let now = context.timestamp.unwrap_or_else(audit::now);
+                !location.is_verified_within(*months, now)
+            }
+            Filter::Accessible(feature) => location.offers_accessibility(&[*feature]),
+            Filter::Cost(cost) => location.service_terms.cost == *cost,
+            Filter::Referral(referral) => location.service_terms.referral == *referral,
+        }
+    }
+}
+
+impl Query {
+    pub fn matches(&self, location: &Location, context: &QueryContext) -> bool {
+        match self {
+            Query::Filter(filter) => filter.matches(location, context),
+            Query::Not(query) => !query.matches(location, context),
+            Query::And(left, right) => {
+                left.matches(location, context) && right.matches(location, context)
+            }
+            Query::Or(left, right) => {
+                left.matches(location, context) || right.matches(location, context)
+            }
+        }
+    }
+
+    fn precedence(&self) -> u8 {
+        match self {
+            Query::Or(..) => 0,
+            Query::And(..) => 1,
+            Query::Not(..) | Query::Filter(..) => 2,
+        }
+    }
+
+    fn fmt_operand(&self, f: &mut fmt::Formatter<'_>, min_precedence: u8) -> fmt::Result {
+        match self.precedence() < min_precedence {
+            true => write!(f, "({self})"),
+            false => write!(f, "{self}"),
+        }
+    }
+}
+
+fn fmt_value(f: &mut fmt::Formatter<'_>, value: &str) -> fmt::Result {
+    let needs_quotes = value.chars().any(|c| !is_word_char(c) || c == ':')
+        || matches!(value, "AND" | "OR" | "NOT");
+    match needs_quotes {
+        true => write!(f, "\"{value}\""),
+        false => write!(f, "{value}"),
+    }
+}
+
+impl fmt::Display for Filter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (field, value) = match self {
+            Filter::Tag(value) => ("tag:", value.clone()),
+            Filter::Name(value) => ("name:", value.clone()),
+            Filter::Address(value) => ("address:", value.clone()),
+            Filter::City(value) => ("city:", value.clone()),
+            Filter::PostalCode(value) => ("postal:", value.clone()),
+            Filter::Description(value) => ("description:", value.clone()),
+            Filter::Text(value) => ("", value.clone()),
+            Filter::Open(OpenedAt::Now) => ("open:", "now".to_string()),
+            Filter::Open(OpenedAt::Day(day)) => ("open:", format!("{day:?}").to_lowercase()),
+            Filter::Unverified(months) => ("unverified:", months.to_string()),
+            Filter::Accessible(feature) => ("accessible:", feature.key().to_string()),
+            Filter::Cost(cost) => ("cost:", cost.key().to_string()),
+            Filter::Referral(referral) => ("referral:", referral.key().to_string()),
+        };
+        write!(f, "{field}")?;
+        fmt_value(f, &value)
+    }
+}
+
+/// Canonical text of the query, which parses back to the same query.
+impl fmt::Display for Query {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Query::Filter(filter) => write!(f, "{filter}"),
+            Query::Not(query) => {
+                write!(f, "NOT ")?;
+                query.fmt_operand(f, 2)
+            }
+            Query::And(left, right) => {
+                left.fmt_operand(f, 1)?;
+                write!(f, " AND ")?;
+                right.fmt_operand(f, 2)
+            }
+            Query::Or(left, right) => {
+                left.fmt_operand(f, 0)?;
+                write!(f, " OR ")?;
+                right.fmt_operand(f, 1)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{address::Address, time::TimeSpan};
+
+    fn filter(query: &str) -> Box<Query> {
+        Box::new(Query::Filter(match query.split_once(':') {
+            Some(("tag", value)) => Filter::Tag(value.to_string()),
+            Some(("open", "now")) => Filter::Open(OpenedAt::Now),
+            _ => Filter::Text(query.to_string()),
+        }))
+    }
+
+    #[test]
+    fn parses_and_evaluates_queries() {
+        let query: Query = "tag:LGBT AND tag:Gdańsk AND open:now AND NOT tag:NFZ"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            query,
+            Query::And(
+                Box::new(Query::And(
+                    Box::new(Query::And(filter("tag:LGBT"), filter("tag:Gdańsk"))),
+                    filter("open:now")
+                )),
+                Box::new(Query::Not(filter("tag:NFZ")))
+            )
+        );
+
+        let mut location = Location::default();
+        location.tags.define_tag("LGBT");
+        location.tags.define_tag("Gdańsk");
+        location.opened_hours.set_day_time_span(
+            Day::Monday,
+            TimeSpan {
+                from: TimePoint { hour: 8, minute: 0 },
+                to: TimePoint {
+                    hour: 16,
+                    minute: 0,
+                },
+            },
+        );
+        let mut context = QueryContext {
+            now: Some((
+                Day::Monday,
+                TimePoint {
+                    hour: 12,
+                    minute: 30,
+                },
+            )),
+            timestamp: Some(Timestamp(1_740_787_200_000)),
+        };
+        assert!(query.matches(&location, &context));
+        assert!("tag:gdansk open:poniedziałek"
+            .parse::<Query>()
+            .unwrap()
+            .matches(&location, &context));
+        location.address = Address::from_free_form("ul. Długa 5, 80-001 Gdańsk");
+        assert!("city:gdansk postal:80-"
+            .parse::<Query>()
+            .unwrap()
+            .matches(&location, &context));
+
+        context.now = Some((
+            Day::Tuesday,
+            TimePoint {
+                hour: 12,
+                minute: 0,
+            },
+        ));
+        assert!(!query.matches(&location, &context));
+        location.tags.define_tag("NFZ");
+        assert!(!query.matches(&location, &QueryContext::default()));
+
+        let unverified: Query = "unverified:6".parse().unwrap();
+        assert!(unverified.matches(&location, &context));
+        location.last_verified_at = context.timestamp.map(|now| now.months_before(5));
+        assert!(!unverified.matches(&location, &context));
+    }
+
+    #[test]
+    fn parse_errors_carry_positions() {
+        let error = |query: &str| query.parse::<Query>().unwrap_err();
+        assert_eq!(
+            error("tag:LGBT AND"),
+            QueryError {
+                position: 12,
+                kind: QueryErrorKind::UnexpectedEnd
+            }
+        );
+        assert_eq!(
+            error("tag:LGBT OR country:Polska"),
+            QueryError {
+                position: 12,
+                kind: QueryErrorKind::UnknownField("country".to_string())
+            }
+        );
+        assert_eq!(
+            error("(tag:LGBT))"),
+            QueryError {
+                position: 10,
+                kind: QueryErrorKind::UnexpectedToken(")".to_string())
+            }
+        );
+        assert_eq!(error("tag:\"domestic abuse").position, 4);
+        assert_eq!(
+            error("open:someday").kind,
+            QueryErrorKind::InvalidValue("someday".to_string())
+        );
+    }
+
+    #[test]
+    fn queries_roundtrip_through_text() {
+        for text in [
+            "tag:LGBT AND (tag:Gdańsk OR tag:Sopot)",
+            "NOT (tag:NFZ OR tag:\"domestic abuse\") AND open:monday",
+            "schronisko OR name:terapia AND address:\"ul. Długa\"",
+            "city:Gdańsk AND NOT postal:80-001",
+            "tag:Gdańsk AND unverified:12",
+            "accessible:step-free OR accessible:pjm",
+            "cost:free AND NOT referral:nfz",
+        ] {
+            let query: Query = text.parse().unwrap();
+            assert_eq!(query.to_string(), text);
+            assert_eq!(query.to_string().parse::<Query>().unwrap(), query);
+        }
+    }
+}
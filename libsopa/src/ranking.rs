@@ -0,0 +1,217 @@
+/*
+Copyright (C) 2025 Mateusz Mazur (Mazurel) <mateusz.mazur@e.email>
+
+This program is free software; you can redistribute it and/or
+modify it under the terms of the GNU General Public License
+as published by the Free Software Foundation; either version 2
+of the License, or (at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program; if not, see
+<https://www.gnu.org/licenses/>.
+*/
+
+use crate::{
+    locations::{Location, Locations},
+    tags::{Tag, TagGroup, TagPreference},
+};
+use std::{cmp::Ordering, collections::HashMap};
+
+const DEFAULT_WEIGHT: f32 = 1.0;
+
+/// Importance of preferred tags, per `TagGroup`.
+/// Tags without group and groups without explicit weight use `DEFAULT_WEIGHT`.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct RankingWeights {
+    group_weights: HashMap<TagGroup, f32>,
+}
+
+impl RankingWeights {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_group_weight(mut self, group: TagGroup, weight: f32) -> Self {
+        self.group_weights.insert(group, weight);
+        self
+    }
+
+    pub fn weight_of(&self, tag: &Tag) -> f32 {
+        tag.get_associated_group()
+            .and_then(|group| self.group_weights.get(&group).copied())
+            .unwrap_or(DEFAULT_WEIGHT)
+    }
+}
+
+/// Why location got its score: which of the wanted tags it has.
+/// Need is a clause of wanted tags, see `MatchingMode::clauses`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct RankingExplanation {
+    pub matched: Vec<Tag>,
+    /// Tags of the needs that are not met.
+    pub missing: Vec<Tag>,
+    pub matched_needs_count: usize,
+    pub needs_count: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RankedLocation {
+    pub location: Location,
+    /// Weighted fraction of preferred tags the location has, between 0 and 1.
+    /// `Locations::search_in_order` adds normalized text relevance on top of it.
+    pub score: f32,
+    pub explanation: RankingExplanation,
+}
+
+impl RankedLocation {
+    /// Scores `location` by wanted tags of the preference, see `TagPreference::wanted`.
+    pub fn new(
+        location: &Location,
+        tags_preference: &TagPreference,
+        weights: &RankingWeights,
+    ) -> Self {
+        let mut explanation = RankingExplanation::default();
+        let mut matched_weight = 0.0;
+        let mut total_weight = 0.0;
+
+        let wanted_tags = tags_preference.wanted();
+        for clause in tags_preference.matching_mode().clauses(&wanted_tags) {
+            // Tags in the clause share the group, so they share the weight
+            let weight = weights.weight_of(clause[0]);
+            total_weight += weight;
+            explanation.needs_count += 1;
+            if location.tags.satisfies(&clause) {
+                matched_weight += weight;
+                explanation.matched_needs_count += 1;
+                explanation.matched.extend(
+                    clause
+                        .into_iter()
+                        .filter(|tag| location.tags.has_tag(tag))
+                        .cloned(),
+                );
+            } else {
+                explanation.missing.extend(clause.into_iter().cloned());
+            }
+        }
+
+        let score = match total_weight > 0.0 {
+            true => matched_weight / total_weight,
+            false => 0.0,
+        };
+        RankedLocation {
+            location: location.clone(),
+            score,
+            explanation,
+        }
+    }
+
+    /// Higher score first, ties are broken by name and then by ID,
+    /// so that the order does not depend on the database layout.
+    pub fn cmp_rank(&self, other: &RankedLocation) -> Ordering {
+        other
+            .score
+            .total_cmp(&self.score)
+            .then_with(|| {
+                let (name, other_name) = (&self.location.name, &other.location.name);
+                name.default_text().cmp(other_name.default_text())
+            })
+            .then_with(|| self.location.get_id().cmp(&other.location.get_id()))
+    }
+}
+
+/// Sorts ranked locations in place, see `RankedLocation::cmp_rank`.
+pub fn sort_by_rank(ranked_locations: &mut [RankedLocation]) {
+    ranked_locations.sort_by(RankedLocation::cmp_rank);
+}
+
+impl Locations {
+    /// Orders locations accepted by `tags_preference` by how well they match it.
+    pub fn rank(
+        &self,
+        tags_preference: &TagPreference,
+        weights: &RankingWeights,
+    ) -> Vec<RankedLocation> {
+        let mut ranked_locations: Vec<RankedLocation> = self
+            .locations
+            .values()
+            .filter(|location| tags_preference.accepts(&location.tags))
+            .map(|location| RankedLocation::new(location, tags_preference, weights))
+            .collect();
+        sort_by_rank(&mut ranked_locations);
+        ranked_locations
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tags::{TagPreferenceKind, Tags};
+
+    fn names(ranked_locations: &[RankedLocation]) -> Vec<&str> {
+        ranked_locations
+            .iter()
+            .map(|ranked| ranked.location.name.default_text())
+            .collect()
+    }
+
+    #[test]
+    fn ranking_is_weighted_and_deterministic() {
+        let mut locations = Locations::new();
+        for (name, tags) in [
+            ("B", vec!["LGBT"]),
+            ("A", vec!["LGBT"]),
+            ("Gdańsk", vec!["Gdańsk"]),
+            ("None", vec![]),
+        ] {
+            locations.push_new(|loc| {
+                loc.name = name.into();
+                loc.tags = Tags::new_tags(tags);
+            });
+        }
+        let mut preference = TagPreference::from(Tags::new_tags(["LGBT", "Gdańsk"]));
+
+        let ranked = locations.rank(&preference, &RankingWeights::new());
+        assert_eq!(names(&ranked), vec!["A", "B", "Gdańsk", "None"]);
+        assert_eq!(ranked[0].score, 0.5);
+
+        let weights = RankingWeights::new().with_group_weight(TagGroup::GeoLocation, 3.0);
+        let ranked = locations.rank(&preference, &weights);
+        assert_eq!(names(&ranked), vec!["Gdańsk", "A", "B", "None"]);
+        assert_eq!(ranked[0].score, 0.75);
+
+        let lgbt = Tag::new("LGBT".to_string());
+        preference.set(&lgbt, Some(TagPreferenceKind::Excluded));
+        let ranked = locations.rank(&preference, &weights);
+        assert_eq!(names(&ranked), vec!["Gdańsk", "None"]);
+    }
+
+    #[test]
+    fn ranking_explains_matched_and_missing_tags() {
+        let mut location = Location::default();
+        location.tags = Tags::new_tags(["LGBT", "NGO"]);
+
+        let preference = TagPreference::from(Tags::new_tags(["LGBT", "Gdańsk", "Gdynia"]));
+        let ranked = RankedLocation::new(&location, &preference, &RankingWeights::new());
+        assert_eq!(
+            ranked.explanation.matched,
+            vec![Tag::new("LGBT".to_string())]
+        );
+        assert_eq!(
+            ranked.explanation.missing,
+            vec![
+                Tag::new("Gdańsk".to_string()),
+                Tag::new("Gdynia".to_string())
+            ]
+        );
+        assert_eq!(ranked.explanation.matched_needs_count, 1);
+        assert_eq!(ranked.explanation.needs_count, 2);
+
+        let ranked = RankedLocation::new(&location, &TagPreference::new(), &RankingWeights::new());
+        assert_eq!(ranked.score, 0.0);
+    }
+}
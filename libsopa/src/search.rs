@@ -0,0 +1,330 @@
+/*
+Copyright (C) 2025 Mateusz Mazur (Mazurel) <mateusz.mazur@e.email>
+
+This program is free software; you can redistribute it and/or
+modify it under the terms of the GNU General Public License
+as published by the Free Software Foundation; either version 2
+of the License, or (at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program; if not, see
+<https://www.gnu.org/licenses/>.
+*/
+
+use crate::{
+    fuzzy::{closest_match, is_fuzzy_match},
+    locations::{Location, Locations},
+    ranking::{sort_by_rank, RankedLocation, RankingWeights},
+    tags::TagPreference,
+};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+const NAME_WEIGHT: f32 = 3.0;
+const TAGS_WEIGHT: f32 = 2.0;
+const ADDRESS_WEIGHT: f32 = 1.5;
+const DESCRIPTION_WEIGHT: f32 = 1.0;
+/// Weight of query term matching only the beginning of indexed term,
+/// so that results show up while the word is still being typed.
+const PREFIX_MATCH_WEIGHT: f32 = 0.5;
+/// Weight of query term matching indexed term with a typo.
+const FUZZY_MATCH_WEIGHT: f32 = 0.3;
+
+/// Stems shorter than this are left as they are.
+const MIN_STEM_LENGTH: usize = 3;
+/// Common Polish and English inflection suffixes (after folding diacritics),
+/// the longest ones first.
+const SUFFIXES: &[&str] = &[
+    "owie", "ach", "ami", "ego", "emu", "ych", "ymi", "iem", "ies", "ing", "om", "ow", "ie", "ii",
+    "ia", "em", "ej", "es", "a", "e", "i", "o", "u", "y", "s",
+];
+
+/// Lowercases `text` and replaces Polish letters with their ASCII counterparts.
+///
+/// ```rust
+/// assert_eq!(libsopa::search::fold_diacritics("Gdańsk, Łódź"), "gdansk, lodz");
+/// ```
+pub fn fold_diacritics(text: &str) -> String {
+    text.to_lowercase()
+        .chars()
+        .map(|c| match c {
+            'ą' => 'a',
+            'ć' => 'c',
+            'ę' => 'e',
+            'ł' => 'l',
+            'ń' => 'n',
+            'ó' => 'o',
+            'ś' => 's',
+            'ź' | 'ż' => 'z',
+            c => c,
+        })
+        .collect()
+}
+
+/// Strips inflection suffix from already folded `word`.
+fn stem(word: &str) -> &str {
+    SUFFIXES
+        .iter()
+        .filter_map(|suffix| word.strip_suffix(suffix))
+        .find(|stem| stem.chars().count() >= MIN_STEM_LENGTH)
+        .unwrap_or(word)
+}
+
+fn words(text: &str) -> impl Iterator<Item = &str> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+}
+
+fn term_of_word(word: &str) -> String {
+    stem(&fold_diacritics(word)).to_string()
+}
+
+/// Splits `text` into normalized search terms.
+pub fn terms(text: &str) -> Vec<String> {
+    words(text).map(term_of_word).collect()
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchHit {
+    pub id: Uuid,
+    pub score: f32,
+}
+
+/// Inverted index over name, description, address and tags of the locations.
+/// Tags are indexed under their names in every available language.
+#[derive(Debug, Clone, Default)]
+pub struct SearchIndex {
+    /// Weight of every term in every location containing it.
+    postings: HashMap<String, HashMap<Uuid, f32>>,
+    /// Word as it was written for every term, used for suggestions.
+    spellings: HashMap<String, String>,
+    locations_count: usize,
+}
+
+impl SearchIndex {
+    pub fn new(locations: &Locations) -> Self {
+        let mut index = SearchIndex::default();
+        for location in locations.locations.values() {
+            index.add_location(location);
+        }
+        index
+    }
+
+    fn add_location(&mut self, location: &Location) {
+        let id = location.get_id();
+        for name in location.name.texts() {
+            self.add_text(id, name, NAME_WEIGHT);
+        }
+        self.add_text(id, &location.address.to_string(), ADDRESS_WEIGHT);
+        for description in location.description.texts() {
+            self.add_text(id, description, DESCRIPTION_WEIGHT);
+        }
+        for tag in location.tags.get_all_tags() {
+            self.add_text(id, &tag.name, TAGS_WEIGHT);
+            for name in tag.human_readable_in_every_locale() {
+                self.add_text(id, &name, TAGS_WEIGHT);
+            }
+        }
+        self.locations_count += 1;
+    }
+
+    fn add_text(&mut self, id: Uuid, text: &str, weight: f32) {
+        for word in words(text) {
+            let term = term_of_word(word);
+            self.spellings
+                .entry(term.clone())
+                .or_insert_with(|| word.to_lowercase());
+            let weights = self.postings.entry(term).or_default();
+            // The same word repeated (e.g. tag name equal in all languages)
+            // should not dominate the score.
+            let term_weight = weights.entry(id).or_insert(0.0);
+            *term_weight = term_weight.max(weight);
+        }
+    }
+
+    /// Rarer terms are more relevant.
+    fn inverse_document_frequency(&self, weights: &HashMap<Uuid, f32>) -> f32 {
+        (1.0 + self.locations_count as f32 / weights.len() as f32).ln()
+    }
+
+    /// Finds locations relevant to the `query`, the most relevant first.
+    /// Locations matching none of the query terms are not returned.
+    pub fn search(&self, query: &str) -> Vec<SearchHit> {
+        let mut scores: HashMap<Uuid, f32> = HashMap::new();
+
+        for query_term in terms(query) {
+            for (term, weights) in self.postings.iter() {
+                let match_weight = if *term == query_term {
+                    1.0
+                } else if term.starts_with(&query_term) {
+                    PREFIX_MATCH_WEIGHT
+                } else if is_fuzzy_match(&query_term, term) {
+                    FUZZY_MATCH_WEIGHT
+                } else {
+                    continue;
+                };
+
+                let idf = self.inverse_document_frequency(weights);
+                for (id, weight) in weights.iter() {
+                    *scores.entry(*id).or_insert(0.0) += weight * idf * match_weight;
+                }
+            }
+        }
+
+        let mut hits: Vec<SearchHit> = scores
+            .into_iter()
+            .map(|(id, score)| SearchHit { id, score })
+            .collect();
+        hits.sort_by(|h1, h2| h2.score.total_cmp(&h1.score).then(h1.id.cmp(&h2.id)));
+        hits
+    }
+
+    fn is_known_term(&self, query_term: &str) -> bool {
+        self.postings
+            .keys()
+            .any(|term| term.starts_with(query_term))
+    }
+
+    /// "Did you mean" suggestion: `query` with words not present in the index
+    /// replaced by the closest known ones. Returns `None` if nothing was corrected.
+    pub fn suggest(&self, query: &str) -> Option<String> {
+        let mut corrected = false;
+        let mut sorted_terms: Vec<&str> = self.postings.keys().map(String::as_str).collect();
+        // Keeps the suggestion stable between calls
+        sorted_terms.sort();
+
+        let suggestion: Vec<String> = words(query)
+            .map(|word| {
+                let query_term = term_of_word(word);
+                if self.is_known_term(&query_term) {
+                    return word.to_string();
+                }
+                match closest_match(&query_term, sorted_terms.iter().copied()) {
+                    Some(term) => {
+                        corrected = true;
+                        self.spellings[term].clone()
+                    }
+                    None => word.to_string(),
+                }
+            })
+            .collect();
+
+        corrected.then(|| suggestion.join(" "))
+    }
+}
+
+impl Locations {
+    /// Orders locations matching the `query` by relevance combined with
+    /// the tags preference ranking. Empty query falls back to `Locations::rank`.
+    pub fn search_in_order(
+        &self,
+        index: &SearchIndex,
+        query: &str,
+        tags_preference: &TagPreference,
+        weights: &RankingWeights,
+    ) -> Vec<RankedLocation> {
+        if terms(query).is_empty() {
+            return self.rank(tags_preference, weights);
+        }
+
+        let hits = index.search(query);
+        let max_score = hits.first().map(|hit| hit.score).unwrap_or(1.0);
+
+        let mut ranked_locations: Vec<RankedLocation> = hits
+            .into_iter()
+            .filter_map(|hit| {
+                let location = self
+                    .locations
+                    .get(&hit.id)
+                    .filter(|location| tags_preference.accepts(&location.tags))?;
+                let mut ranked = RankedLocation::new(location, tags_preference, weights);
+                ranked.score += hit.score / max_score;
+                Some(ranked)
+            })
+            .collect();
+        sort_by_rank(&mut ranked_locations);
+        ranked_locations
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tags::Tags;
+
+    #[test]
+    fn terms_are_folded_and_stemmed() {
+        assert_eq!(terms("Schronisko"), terms("schroniskach"));
+        assert_eq!(terms("terapią"), terms("Terapia"));
+        assert_eq!(terms("Gdańsk"), vec!["gdansk"]);
+        assert_eq!(terms("ul. Długa 5"), vec!["ul", "dlug", "5"]);
+    }
+
+    #[test]
+    fn search_ranks_by_relevance() {
+        let mut locations = Locations::new();
+        let in_name = locations.push_new(|loc| loc.name = "Schronisko dla kobiet".into());
+        let in_description = locations.push_new(|loc| {
+            loc.name = "Centrum pomocy".into();
+            loc.description = "Prowadzimy schronisko".into();
+        });
+        let by_tag = locations.push_new(|loc| {
+            loc.name = "Noclegownia".into();
+            loc.tags.define_tag("homelessness crisis");
+        });
+        let index = SearchIndex::new(&locations);
+
+        let hits = index.search("schroniska");
+        assert_eq!(
+            hits.iter().map(|hit| hit.id).collect::<Vec<_>>(),
+            vec![in_name.get_id(), in_description.get_id()]
+        );
+        assert_eq!(index.search("bezdomnosc")[0].id, by_tag.get_id());
+        assert_eq!(index.search("homeless")[0].id, by_tag.get_id());
+    }
+
+    #[test]
+    fn misspelled_query_finds_close_matches() {
+        let mut locations = Locations::new();
+        let location = locations.push_new(|loc| {
+            loc.name = "Poradnia".into();
+            loc.tags.define_tag("Gdańsk");
+            loc.tags.define_tag("alcohol abuse");
+        });
+        let index = SearchIndex::new(&locations);
+
+        assert_eq!(index.search("alkohol")[0].id, location.get_id());
+        assert_eq!(index.search("gdnsk")[0].id, location.get_id());
+        assert_eq!(
+            index.suggest("poradnie gdnsk"),
+            Some("poradnie gdańsk".to_string())
+        );
+        assert_eq!(index.suggest("poradnia"), None);
+    }
+
+    #[test]
+    fn search_combines_text_and_tags_score() {
+        let mut locations = Locations::new();
+        locations.push_new(|loc| loc.name = "Terapia".into());
+        let tagged = locations.push_new(|loc| {
+            loc.name = "Terapia".into();
+            loc.tags.define_tag("LGBT");
+        });
+        locations.push_new(|loc| loc.name = "Prawnik".into());
+        let index = SearchIndex::new(&locations);
+
+        let results = locations.search_in_order(
+            &index,
+            "terapie",
+            &TagPreference::from(Tags::new_tags(["LGBT"])),
+            &RankingWeights::new(),
+        );
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].location, tagged);
+    }
+}
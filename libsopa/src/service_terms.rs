@@ -0,0 +1,266 @@
+/*
+Copyright (C) 2025 Mateusz Mazur (Mazurel) <mateusz.mazur@e.email>
+
+This program is free software; you can redistribute it and/or
+modify it under the terms of the GNU General Public License
+as published by the Free Software Foundation; either version 2
+of the License, or (at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program; if not, see
+<https://www.gnu.org/licenses/>.
+*/
+
+//! Terms on which a location provides its services: cost, referral and eligibility.
+
+use crate::{audit::Timestamp, locations::Location};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum CostKind {
+    #[default]
+    Unknown,
+    Free,
+    /// Voluntary donation or a fee depending on income.
+    PayWhatYouCan,
+    Paid,
+}
+
+impl CostKind {
+    pub const ALL: [CostKind; 4] = [
+        CostKind::Unknown,
+        CostKind::Free,
+        CostKind::PayWhatYouCan,
+        CostKind::Paid,
+    ];
+
+    /// Name used in queries, e.g. `cost:free`.
+    pub fn key(&self) -> &'static str {
+        match self {
+            CostKind::Unknown => "unknown",
+            CostKind::Free => "free",
+            CostKind::PayWhatYouCan => "pay-what-you-can",
+            CostKind::Paid => "paid",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<CostKind> {
+        Self::ALL.into_iter().find(|cost| cost.key() == key)
+    }
+}
+
+impl fmt::Display for CostKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let cost = match self {
+            CostKind::Unknown => t!("cost-unknown"),
+            CostKind::Free => t!("cost-free"),
+            CostKind::PayWhatYouCan => t!("cost-pay-what-you-can"),
+            CostKind::Paid => t!("cost-paid"),
+        };
+        write!(f, "{cost}")
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ReferralRequirement {
+    #[default]
+    Unknown,
+    NotRequired,
+    /// Referral within the public health care (NFZ).
+    NfzReferral,
+    /// E.g. from a social welfare centre (OPS) or a court.
+    OtherReferral,
+}
+
+impl ReferralRequirement {
+    pub const ALL: [ReferralRequirement; 4] = [
+        ReferralRequirement::Unknown,
+        ReferralRequirement::NotRequired,
+        ReferralRequirement::NfzReferral,
+        ReferralRequirement::OtherReferral,
+    ];
+
+    /// Name used in queries, e.g. `referral:none`.
+    pub fn key(&self) -> &'static str {
+        match self {
+            ReferralRequirement::Unknown => "unknown",
+            ReferralRequirement::NotRequired => "none",
+            ReferralRequirement::NfzReferral => "nfz",
+            ReferralRequirement::OtherReferral => "other",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<ReferralRequirement> {
+        Self::ALL.into_iter().find(|referral| referral.key() == key)
+    }
+}
+
+impl fmt::Display for ReferralRequirement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let referral = match self {
+            ReferralRequirement::Unknown => t!("referral-unknown"),
+            ReferralRequirement::NotRequired => t!("referral-not-required"),
+            ReferralRequirement::NfzReferral => t!("referral-nfz"),
+            ReferralRequirement::OtherReferral => t!("referral-other"),
+        };
+        write!(f, "{referral}")
+    }
+}
+
+/// Who can use the services, empty criteria mean everyone.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Eligibility {
+    #[serde(default)]
+    pub min_age: Option<u8>,
+    #[serde(default)]
+    pub max_age: Option<u8>,
+    /// E.g. "residents of Gdańsk", empty when there are no residency rules.
+    #[serde(default)]
+    pub residency: String,
+}
+
+impl Eligibility {
+    pub fn is_age_eligible(&self, age: u8) -> bool {
+        self.min_age.is_none_or(|min_age| min_age <= age)
+            && self.max_age.is_none_or(|max_age| age <= max_age)
+    }
+
+    pub fn has_valid_age_range(&self) -> bool {
+        match (self.min_age, self.max_age) {
+            (Some(min_age), Some(max_age)) => min_age <= max_age,
+            _ => true,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.min_age.is_none() && self.max_age.is_none() && self.residency.trim().is_empty()
+    }
+}
+
+/// Human-readable criteria, e.g. "age 18-26, residents of Gdańsk".
+impl fmt::Display for Eligibility {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let age = match (self.min_age, self.max_age) {
+            (Some(min), Some(max)) => Some(t!("eligibility-age-range", min = min, max = max)),
+            (Some(min), None) => Some(t!("eligibility-age-from", min = min)),
+            (None, Some(max)) => Some(t!("eligibility-age-up-to", max = max)),
+            (None, None) => None,
+        };
+        let residency = Some(self.residency.trim())
+            .filter(|residency| !residency.is_empty())
+            .map(|residency| t!("eligibility-residency", residency = residency));
+        let criteria: Vec<String> = age
+            .into_iter()
+            .chain(residency)
+            .map(|criterion| criterion.to_string())
+            .collect();
+        match criteria.is_empty() {
+            true => write!(f, "{}", t!("eligibility-everyone")),
+            false => write!(f, "{}", criteria.join(", ")),
+        }
+    }
+}
+
+/// Typical waiting time for the first appointment, as reported on the given date.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WaitingTime {
+    pub days: u32,
+    pub as_of: Timestamp,
+}
+
+impl fmt::Display for WaitingTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let waiting_time = match self.days {
+            0 => t!("waiting-time-none", date = self.as_of),
+            days => t!("waiting-time-days", days = days, date = self.as_of),
+        };
+        write!(f, "{waiting_time}")
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct ServiceTerms {
+    #[serde(default)]
+    pub cost: CostKind,
+    #[serde(default)]
+    pub referral: ReferralRequirement,
+    #[serde(default)]
+    pub eligibility: Eligibility,
+    #[serde(default)]
+    pub waiting_time: Option<WaitingTime>,
+}
+
+/// Known terms joined in a single line, used e.g. in the changelog.
+impl fmt::Display for ServiceTerms {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut terms = vec![self.cost.to_string(), self.referral.to_string()];
+        if !self.eligibility.is_empty() {
+            terms.push(self.eligibility.to_string());
+        }
+        if let Some(waiting_time) = self.waiting_time {
+            terms.push(waiting_time.to_string());
+        }
+        write!(f, "{}", terms.join(", "))
+    }
+}
+
+impl Location {
+    /// Only locations confirmed to be free, unknown cost does not count.
+    pub fn is_free(&self) -> bool {
+        self.service_terms.cost == CostKind::Free
+    }
+
+    /// Only locations confirmed not to require a referral.
+    pub fn needs_no_referral(&self) -> bool {
+        self.service_terms.referral == ReferralRequirement::NotRequired
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn age_eligibility_respects_bounds() {
+        let eligibility = Eligibility {
+            min_age: Some(13),
+            max_age: Some(25),
+            residency: String::new(),
+        };
+        assert!(!eligibility.is_age_eligible(12));
+        assert!(eligibility.is_age_eligible(13));
+        assert!(eligibility.is_age_eligible(25));
+        assert!(!eligibility.is_age_eligible(26));
+        assert!(Eligibility::default().is_age_eligible(80));
+
+        let reversed = Eligibility {
+            min_age: Some(30),
+            max_age: Some(18),
+            residency: String::new(),
+        };
+        assert!(!reversed.has_valid_age_range());
+    }
+
+    #[test]
+    fn unknown_terms_do_not_pass_filters() {
+        let mut location = Location::default();
+        assert!(!location.is_free());
+        assert!(!location.needs_no_referral());
+
+        location.service_terms.cost = CostKind::Free;
+        location.service_terms.referral = ReferralRequirement::NotRequired;
+        assert!(location.is_free());
+        assert!(location.needs_no_referral());
+        assert_eq!(CostKind::from_key("free"), Some(CostKind::Free));
+        assert_eq!(
+            ReferralRequirement::from_key("nfz"),
+            Some(ReferralRequirement::NfzReferral)
+        );
+    }
+}
@@ -0,0 +1,91 @@
+/*
+Copyright (C) 2025 Mateusz Mazur (Mazurel) <mateusz.mazur@e.email>
+
+This program is free software; you can redistribute it and/or
+modify it under the terms of the GNU General Public License
+as published by the Free Software Foundation; either version 2
+of the License, or (at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program; if not, see
+<https://www.gnu.org/licenses/>.
+*/
+
+use super::types::{Tag, TagGroup, Tags};
+
+/// How selected tags are combined when matching them against location tags.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MatchingMode {
+    /// Every selected tag has to be matched on its own.
+    Flat,
+    /// Selected tags of the same `TagGroup` are alternatives ("any of these cities"),
+    /// while different groups and ungrouped tags all have to be matched.
+    #[default]
+    GroupAware,
+}
+
+impl MatchingMode {
+    /// Splits `selected` tags into clauses. Clause is satisfied
+    /// when any of its tags is matched, all clauses should be satisfied.
+    pub fn clauses<'a>(&self, selected: &'a Tags) -> Vec<Vec<&'a Tag>> {
+        let tags = selected.get_all_tags_in_order();
+        match self {
+            MatchingMode::Flat => tags.into_iter().map(|tag| vec![tag]).collect(),
+            MatchingMode::GroupAware => {
+                let mut clauses: Vec<(Option<TagGroup>, Vec<&Tag>)> = vec![];
+                for tag in tags {
+                    let group = tag.get_associated_group();
+                    match clauses
+                        .iter_mut()
+                        .find(|(clause_group, _)| group.is_some() && *clause_group == group)
+                    {
+                        Some((_, clause)) => clause.push(tag),
+                        None => clauses.push((group, vec![tag])),
+                    }
+                }
+                clauses.into_iter().map(|(_, clause)| clause).collect()
+            }
+        }
+    }
+}
+
+impl Tags {
+    pub fn satisfies(&self, clause: &[&Tag]) -> bool {
+        clause.iter().any(|tag| self.has_tag(tag))
+    }
+
+    /// Whether these tags satisfy every clause of `selected`, see `MatchingMode::clauses`.
+    pub fn matches_all(&self, selected: &Tags, mode: MatchingMode) -> bool {
+        mode.clauses(selected)
+            .iter()
+            .all(|clause| self.satisfies(clause))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tags_of_the_same_group_are_alternatives() {
+        let selected = Tags::new_tags(["Gdańsk", "Gdynia", "Adult", "LGBT"]);
+        let location_tags = Tags::new_tags(["Gdańsk", "Adult", "LGBT"]);
+
+        assert_eq!(MatchingMode::GroupAware.clauses(&selected).len(), 3);
+        assert!(location_tags.matches_all(&selected, MatchingMode::GroupAware));
+        assert!(!location_tags.matches_all(&selected, MatchingMode::Flat));
+
+        let elsewhere = Tags::new_tags(["Sopot", "Adult"]);
+        let satisfied = MatchingMode::GroupAware
+            .clauses(&selected)
+            .iter()
+            .filter(|clause| elsewhere.satisfies(clause))
+            .count();
+        assert_eq!(satisfied, 1);
+    }
+}
@@ -17,7 +17,11 @@ along with this program; if not, see
 */
 
 mod macros;
+pub mod matching;
+pub mod preference;
 pub mod types;
+pub use matching::*;
+pub use preference::*;
 pub use types::*;
 
 // Note: These macros are defined in `macros.rs`.
@@ -63,6 +67,16 @@ define_tags!(
     "mental illness"
 );
 
+impl Tag {
+    /// Tag name translated to every available language, see `human_readable`.
+    pub fn human_readable_in_every_locale(&self) -> Vec<String> {
+        rust_i18n::available_locales!()
+            .into_iter()
+            .map(|locale| t!(self.name.as_str(), locale = locale).to_string())
+            .collect()
+    }
+}
+
 pub fn get_all_supported_tags() -> &'static Tags {
     &ALL_DEFINED_TAGS
 }
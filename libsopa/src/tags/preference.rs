@@ -0,0 +1,179 @@
+/*
+Copyright (C) 2025 Mateusz Mazur (Mazurel) <mateusz.mazur@e.email>
+
+This program is free software; you can redistribute it and/or
+modify it under the terms of the GNU General Public License
+as published by the Free Software Foundation; either version 2
+of the License, or (at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program; if not, see
+<https://www.gnu.org/licenses/>.
+*/
+
+use super::{
+    matching::MatchingMode,
+    types::{Tag, Tags},
+};
+
+/// How the user feels about a tag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TagPreferenceKind {
+    /// Location must have the tag.
+    Required,
+    /// Locations with the tag are ranked higher.
+    Preferred,
+    /// Location must not have the tag.
+    Excluded,
+}
+
+impl TagPreferenceKind {
+    /// Order in which the preference changes when the tag is clicked:
+    /// none -> preferred -> required -> excluded -> none.
+    pub fn cycle(kind: Option<TagPreferenceKind>) -> Option<TagPreferenceKind> {
+        use TagPreferenceKind::*;
+        match kind {
+            None => Some(Preferred),
+            Some(Preferred) => Some(Required),
+            Some(Required) => Some(Excluded),
+            Some(Excluded) => None,
+        }
+    }
+}
+
+/// Tags the user is looking for, Required and Excluded tags filter the locations,
+/// while Preferred tags only rank them.
+/// Required and Preferred tags are combined according to the `MatchingMode`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct TagPreference {
+    required: Tags,
+    preferred: Tags,
+    excluded: Tags,
+    matching_mode: MatchingMode,
+}
+
+impl From<Tags> for TagPreference {
+    /// All of the `tags` are preferred.
+    fn from(tags: Tags) -> Self {
+        TagPreference {
+            preferred: tags,
+            ..Default::default()
+        }
+    }
+}
+
+impl TagPreference {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self, tag: &Tag) -> Option<TagPreferenceKind> {
+        if self.required.has_tag(tag) {
+            Some(TagPreferenceKind::Required)
+        } else if self.preferred.has_tag(tag) {
+            Some(TagPreferenceKind::Preferred)
+        } else if self.excluded.has_tag(tag) {
+            Some(TagPreferenceKind::Excluded)
+        } else {
+            None
+        }
+    }
+
+    /// Sets preference of the `tag`, `None` removes it.
+    pub fn set(&mut self, tag: &Tag, kind: Option<TagPreferenceKind>) {
+        self.required.undefine_tag(&tag.name);
+        self.preferred.undefine_tag(&tag.name);
+        self.excluded.undefine_tag(&tag.name);
+        let tags = match kind {
+            Some(TagPreferenceKind::Required) => &mut self.required,
+            Some(TagPreferenceKind::Preferred) => &mut self.preferred,
+            Some(TagPreferenceKind::Excluded) => &mut self.excluded,
+            None => return,
+        };
+        tags.define_tag(&tag.name);
+    }
+
+    pub fn with(&self, tag: &Tag, kind: Option<TagPreferenceKind>) -> Self {
+        let mut preference = self.clone();
+        preference.set(tag, kind);
+        preference
+    }
+
+    pub fn with_matching_mode(mut self, matching_mode: MatchingMode) -> Self {
+        self.matching_mode = matching_mode;
+        self
+    }
+
+    pub fn matching_mode(&self) -> MatchingMode {
+        self.matching_mode
+    }
+
+    pub fn required(&self) -> &Tags {
+        &self.required
+    }
+
+    pub fn preferred(&self) -> &Tags {
+        &self.preferred
+    }
+
+    pub fn excluded(&self) -> &Tags {
+        &self.excluded
+    }
+
+    /// Required and preferred tags together, i.e. tags the location should have.
+    pub fn wanted(&self) -> Tags {
+        let mut wanted = self.required.clone();
+        for tag in self.preferred.get_all_tags() {
+            wanted.define_tag(&tag.name);
+        }
+        wanted
+    }
+
+    /// Whether location with `tags` passes Required and Excluded filters.
+    pub fn accepts(&self, tags: &Tags) -> bool {
+        tags.matches_all(&self.required, self.matching_mode)
+            && !self
+                .excluded
+                .get_all_tags()
+                .iter()
+                .any(|tag| tags.has_tag(tag))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn required_and_excluded_tags_filter() {
+        let male = Tag::new("Male".to_string());
+        let nfz = Tag::new("NFZ".to_string());
+        let preference = TagPreference::new()
+            .with(&male, Some(TagPreferenceKind::Required))
+            .with(&nfz, Some(TagPreferenceKind::Excluded));
+
+        assert!(preference.accepts(&Tags::new_tags(["Male", "LGBT"])));
+        assert!(!preference.accepts(&Tags::new_tags(["LGBT"])));
+        assert!(!preference.accepts(&Tags::new_tags(["Male", "NFZ"])));
+
+        let mut kind = preference.get(&nfz);
+        assert_eq!(kind, Some(TagPreferenceKind::Excluded));
+        kind = TagPreferenceKind::cycle(kind);
+        assert_eq!(
+            preference.with(&nfz, kind),
+            TagPreference::new().with(&male, Some(TagPreferenceKind::Required))
+        );
+
+        let female = Tag::new("Female".to_string());
+        let preference = preference.with(&female, Some(TagPreferenceKind::Required));
+        assert!(preference.accepts(&Tags::new_tags(["Female"])));
+        assert!(!preference
+            .with_matching_mode(MatchingMode::Flat)
+            .accepts(&Tags::new_tags(["Female"])));
+    }
+}
@@ -56,7 +56,7 @@ impl Hash for Tag {
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
 pub struct Tags {
     pub tags: HashSet<Tag>,
 }
@@ -138,6 +138,9 @@ impl Tags {
     pub fn overlap(&self, other: &Tags) -> f32 {
         let other_tags = other.get_all_tags();
         let other_tags_amount = other_tags.len();
+        if other_tags_amount == 0 {
+            return 0.0;
+        }
 
         let overlap: f32 = other_tags
             .into_iter()
@@ -181,4 +184,11 @@ mod tests {
         tags.undefine_tag("Test");
         assert!(!tags.has_tag(&tag.clone()));
     }
+
+    #[test]
+    fn overlap_with_no_tags() {
+        let tags = Tags::new_tags(["Test"]);
+        assert_eq!(tags.overlap(&Tags::new()), 0.0);
+        assert_eq!(tags.overlap(&Tags::new_tags(["Test", "Other"])), 0.5);
+    }
 }
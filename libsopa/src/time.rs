@@ -30,7 +30,7 @@ pub enum Day {
     Sunday,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct TimePoint {
     pub hour: u8,
     pub minute: u8,
@@ -85,6 +85,18 @@ impl OpenedHours {
     pub fn is_opened_on_day(&self, day: &Day) -> bool {
         self.get_day_time_span(day).is_some()
     }
+
+    pub fn is_opened_at(&self, day: &Day, time: &TimePoint) -> bool {
+        self.get_day_time_span(day)
+            .is_some_and(|time_span| time_span.contains(time))
+    }
+}
+
+impl TimeSpan {
+    /// Whether `time` is between `from` (inclusive) and `to` (exclusive).
+    pub fn contains(&self, time: &TimePoint) -> bool {
+        self.from <= *time && *time < self.to
+    }
 }
 
 impl TimePoint {
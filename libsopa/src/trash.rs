@@ -0,0 +1,149 @@
+/*
+Copyright (C) 2025 Mateusz Mazur (Mazurel) <mateusz.mazur@e.email>
+
+This program is free software; you can redistribute it and/or
+modify it under the terms of the GNU General Public License
+as published by the Free Software Foundation; either version 2
+of the License, or (at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program; if not, see
+<https://www.gnu.org/licenses/>.
+*/
+
+//! Soft deletion: removed locations are kept in the trash until purged.
+
+use crate::{
+    audit::{self, Timestamp},
+    error::{Error, Result},
+    journal::JournaledLocations,
+    locations::{Location, Locations},
+};
+
+impl Location {
+    pub fn deleted_at(&self) -> Option<Timestamp> {
+        self.deleted_at
+    }
+
+    pub fn is_deleted(&self) -> bool {
+        self.deleted_at.is_some()
+    }
+}
+
+impl Locations {
+    /// Marks the current version of `location` as deleted.
+    pub fn move_to_trash(&mut self, location: &Location) -> Result<Location> {
+        let trashed = self.with_deleted_at(location, Some(audit::now()))?;
+        Ok(self.force_update(trashed))
+    }
+
+    pub fn restore_from_trash(&mut self, location: &Location) -> Result<Location> {
+        let restored = self.with_deleted_at(location, None)?;
+        Ok(self.force_update(restored))
+    }
+
+    /// Removes `location` permanently, only locations in the trash can be purged.
+    pub fn purge(&mut self, location: Location) -> Result<Location> {
+        self.ensure_in_trash(&location)?;
+        self.remove(location)
+    }
+
+    /// Deleted locations, the most recently deleted first.
+    pub fn trash(&self) -> Vec<Location> {
+        let mut trash: Vec<Location> = self
+            .locations
+            .values()
+            .filter(|location| location.is_deleted())
+            .cloned()
+            .collect();
+        trash.sort_by_key(|location| std::cmp::Reverse(location.deleted_at));
+        trash
+    }
+
+    /// Current version of `location` with changed deletion marker.
+    fn with_deleted_at(
+        &self,
+        location: &Location,
+        deleted_at: Option<Timestamp>,
+    ) -> Result<Location> {
+        let mut current = self
+            .locations
+            .get(&location.get_id())
+            .cloned()
+            .ok_or(Error::MissingLocation(location.get_id()))?;
+        current.deleted_at = deleted_at;
+        Ok(current)
+    }
+
+    fn ensure_in_trash(&self, location: &Location) -> Result<()> {
+        match self.locations.get(&location.get_id()) {
+            Some(current) if current.is_deleted() => Ok(()),
+            Some(_) => Err(Error::NotInTrash(location.get_id())),
+            None => Err(Error::MissingLocation(location.get_id())),
+        }
+    }
+}
+
+impl JournaledLocations<'_> {
+    /// See `Locations::move_to_trash`.
+    pub fn move_to_trash(&mut self, location: &Location) -> Result<Location> {
+        let trashed = self.with_deleted_at(location, Some(audit::now()))?;
+        Ok(self.force_update(trashed))
+    }
+
+    /// See `Locations::restore_from_trash`.
+    pub fn restore_from_trash(&mut self, location: &Location) -> Result<Location> {
+        let restored = self.with_deleted_at(location, None)?;
+        Ok(self.force_update(restored))
+    }
+
+    /// See `Locations::purge`.
+    pub fn purge(&mut self, location: Location) -> Result<Location> {
+        self.ensure_in_trash(&location)?;
+        self.remove(location)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::journal::Journal;
+
+    #[test]
+    fn trashed_locations_are_hidden_and_restorable() {
+        let mut locations = Locations::new();
+        let mut journal = Journal::new();
+        let mut journaled = JournaledLocations::new(&mut locations, &mut journal);
+        let location = journaled.push_new(|loc| loc.name = "Example".into());
+        let mut published = location.clone();
+        published.publication_state = crate::publication::PublicationState::Published;
+        let location = journaled.push_update(published).unwrap();
+
+        assert_eq!(
+            journaled.purge(location.clone()),
+            Err(Error::NotInTrash(location.get_id()))
+        );
+        let trashed = journaled.move_to_trash(&location).unwrap();
+        assert!(trashed.is_deleted());
+        assert!(!journaled.published().contains(&location));
+        assert_eq!(journaled.trash(), vec![trashed.clone()]);
+
+        journaled.undo();
+        assert!(journaled.trash().is_empty());
+        journaled.redo();
+
+        let restored = journaled.restore_from_trash(&trashed).unwrap();
+        assert!(journaled.published().contains(&restored));
+
+        journaled.move_to_trash(&restored).unwrap();
+        journaled.purge(restored.clone()).unwrap();
+        assert!(!journaled.contains(&restored));
+        journaled.undo();
+        assert_eq!(journaled.trash().len(), 1);
+    }
+}
@@ -0,0 +1,372 @@
+/*
+Copyright (C) 2025 Mateusz Mazur (Mazurel) <mateusz.mazur@e.email>
+
+This program is free software; you can redistribute it and/or
+modify it under the terms of the GNU General Public License
+as published by the Free Software Foundation; either version 2
+of the License, or (at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program; if not, see
+<https://www.gnu.org/licenses/>.
+*/
+
+use crate::{
+    address::is_postal_code,
+    contact::{Contact, ContactType},
+    locations::Location,
+    tags::{get_all_supported_tags_of_group, TagGroup},
+    time::Day,
+};
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// Location should be fixed, but can be saved.
+    Warning,
+    /// Location cannot be saved.
+    Error,
+}
+
+/// Field of the location an issue refers to.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum FieldPath {
+    Name,
+    Address,
+    PostalCode,
+    /// Index into `ContactMethods::all_contact_methods`.
+    Contact(usize),
+    OpenedHours(Day),
+    Tags,
+    Eligibility,
+}
+
+/// Path as used in the serialized location, e.g. `contact_methods[1]`.
+impl fmt::Display for FieldPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FieldPath::Name => write!(f, "name"),
+            FieldPath::Address => write!(f, "address"),
+            FieldPath::PostalCode => write!(f, "address.postal_code"),
+            FieldPath::Contact(idx) => write!(f, "contact_methods[{idx}]"),
+            FieldPath::OpenedHours(day) => {
+                write!(f, "opened_hours.{}", format!("{day:?}").to_lowercase())
+            }
+            FieldPath::Tags => write!(f, "tags"),
+            FieldPath::Eligibility => write!(f, "service_terms.eligibility"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IssueKind {
+    EmptyName,
+    EmptyAddress,
+    /// Address was not recognized when it was converted from free-form text.
+    AddressNeedsReview,
+    MalformedPostalCode,
+    EmptyContact,
+    MalformedEmail,
+    MalformedPhoneNumber,
+    MalformedWebAddress,
+    /// Opening hours end before they start.
+    TimeSpanEndsBeforeStart,
+    EmptyTimeSpan,
+    NoTags,
+    /// Location will not show up in geo-filtered searches.
+    NoGeoLocationTag,
+    /// Minimal age is greater than the maximal one.
+    InvalidAgeRange,
+}
+
+impl IssueKind {
+    pub fn severity(&self) -> Severity {
+        // Postal codes, phone numbers and web addresses are only warned about,
+        // as they are written in many ways
+        match self {
+            IssueKind::EmptyName
+            | IssueKind::EmptyContact
+            | IssueKind::MalformedEmail
+            | IssueKind::TimeSpanEndsBeforeStart
+            | IssueKind::NoTags
+            | IssueKind::InvalidAgeRange => Severity::Error,
+            IssueKind::EmptyAddress
+            | IssueKind::AddressNeedsReview
+            | IssueKind::MalformedPostalCode
+            | IssueKind::MalformedPhoneNumber
+            | IssueKind::MalformedWebAddress
+            | IssueKind::EmptyTimeSpan
+            | IssueKind::NoGeoLocationTag => Severity::Warning,
+        }
+    }
+}
+
+impl fmt::Display for IssueKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            IssueKind::EmptyName => t!("validation-empty-name"),
+            IssueKind::EmptyAddress => t!("validation-empty-address"),
+            IssueKind::AddressNeedsReview => t!("validation-address-needs-review"),
+            IssueKind::MalformedPostalCode => t!("validation-malformed-postal-code"),
+            IssueKind::EmptyContact => t!("validation-empty-contact"),
+            IssueKind::MalformedEmail => t!("validation-malformed-email"),
+            IssueKind::MalformedPhoneNumber => t!("validation-malformed-phone-number"),
+            IssueKind::MalformedWebAddress => t!("validation-malformed-web-address"),
+            IssueKind::TimeSpanEndsBeforeStart => t!("validation-time-span-ends-before-start"),
+            IssueKind::EmptyTimeSpan => t!("validation-empty-time-span"),
+            IssueKind::NoTags => t!("validation-no-tags"),
+            IssueKind::NoGeoLocationTag => t!("validation-no-geo-location-tag"),
+            IssueKind::InvalidAgeRange => t!("validation-invalid-age-range"),
+        };
+        write!(f, "{message}")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationIssue {
+    pub field: FieldPath,
+    pub kind: IssueKind,
+}
+
+impl ValidationIssue {
+    pub fn severity(&self) -> Severity {
+        self.kind.severity()
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.severity() == Severity::Error
+    }
+}
+
+impl fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.field, self.kind)
+    }
+}
+
+pub fn has_errors(issues: &[ValidationIssue]) -> bool {
+    issues.iter().any(ValidationIssue::is_error)
+}
+
+fn is_valid_email(email: &str) -> bool {
+    match email.split_once('@') {
+        Some((user, domain)) => {
+            !user.is_empty()
+                && !domain.contains('@')
+                && domain.contains('.')
+                && !domain.starts_with('.')
+                && !domain.ends_with('.')
+                && !email.contains(char::is_whitespace)
+        }
+        None => false,
+    }
+}
+
+/// Accepts full numbers and short ones, e.g. helplines like 112 or 116 123.
+/// Digits may be separated by any whitespace or `+-()/`.
+fn is_valid_phone_number(phone_number: &str) -> bool {
+    let digits = phone_number.chars().filter(char::is_ascii_digit).count();
+    let is_allowed = |c: char| c.is_ascii_digit() || c.is_whitespace() || "+-()/".contains(c);
+    phone_number.chars().all(is_allowed)
+        && ((3..=6).contains(&digits) || (9..=15).contains(&digits))
+}
+
+fn is_valid_web_address(web_address: &str) -> bool {
+    let host = web_address
+        .strip_prefix("https://")
+        .or_else(|| web_address.strip_prefix("http://"));
+    host.is_some_and(|host| host.contains('.') && !web_address.contains(char::is_whitespace))
+}
+
+fn validate_contact(contact: &Contact) -> Option<IssueKind> {
+    let value = contact.value.trim();
+    if value.is_empty() {
+        return Some(IssueKind::EmptyContact);
+    }
+    match contact.contact_type {
+        ContactType::Email if !is_valid_email(value) => Some(IssueKind::MalformedEmail),
+        ContactType::PhoneNumber if !is_valid_phone_number(value) => {
+            Some(IssueKind::MalformedPhoneNumber)
+        }
+        ContactType::WebAddress if !is_valid_web_address(value) => {
+            Some(IssueKind::MalformedWebAddress)
+        }
+        _ => None,
+    }
+}
+
+impl Location {
+    /// Lists problems of the location, ordered by field.
+    /// Locations with issues of `Severity::Error` should not be saved.
+    pub fn validate(&self) -> Vec<ValidationIssue> {
+        let mut issues = vec![];
+        let mut issue = |field: FieldPath, kind: IssueKind| {
+            issues.push(ValidationIssue { field, kind });
+        };
+
+        if self.name.is_empty() {
+            issue(FieldPath::Name, IssueKind::EmptyName);
+        }
+
+        if self.address.is_empty() {
+            issue(FieldPath::Address, IssueKind::EmptyAddress);
+        } else if self.address.needs_review {
+            issue(FieldPath::Address, IssueKind::AddressNeedsReview);
+        }
+        let postal_code = self.address.postal_code.trim();
+        if !postal_code.is_empty() && !is_postal_code(postal_code) {
+            issue(FieldPath::PostalCode, IssueKind::MalformedPostalCode);
+        }
+
+        for (idx, contact) in self
+            .contact_methods
+            .all_contact_methods()
+            .iter()
+            .enumerate()
+        {
+            if let Some(kind) = validate_contact(contact) {
+                issue(FieldPath::Contact(idx), kind);
+            }
+        }
+
+        for day in Day::get_all_days_in_week() {
+            if let Some(time_span) = self.opened_hours.get_day_time_span(&day) {
+                if time_span.to < time_span.from {
+                    issue(
+                        FieldPath::OpenedHours(day),
+                        IssueKind::TimeSpanEndsBeforeStart,
+                    );
+                } else if time_span.to == time_span.from {
+                    issue(FieldPath::OpenedHours(day), IssueKind::EmptyTimeSpan);
+                }
+            }
+        }
+
+        if self.tags.get_all_tags().is_empty() {
+            issue(FieldPath::Tags, IssueKind::NoTags);
+        } else if !get_all_supported_tags_of_group(&TagGroup::GeoLocation)
+            .get_all_tags()
+            .iter()
+            .any(|tag| self.tags.has_tag(tag))
+        {
+            issue(FieldPath::Tags, IssueKind::NoGeoLocationTag);
+        }
+
+        if !self.service_terms.eligibility.has_valid_age_range() {
+            issue(FieldPath::Eligibility, IssueKind::InvalidAgeRange);
+        }
+
+        issues
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        address::Address,
+        tags::Tag,
+        time::{TimePoint, TimeSpan},
+    };
+
+    fn valid_location() -> Location {
+        let mut location = Location::default();
+        location.name = "Punkt Interwencji Kryzysowej".into();
+        location.address = Address::from_free_form("ul. Długa 5, 80-001 Gdańsk");
+        location.tags.define_tag("Gdańsk");
+        for (contact_type, value) in [
+            (ContactType::Email, "kontakt@example.org"),
+            (ContactType::PhoneNumber, "+48 58 123 45 67"),
+            (ContactType::WebAddress, "https://example.org/pik"),
+        ] {
+            location.contact_methods.add_new_contact_method(Contact {
+                contact_type,
+                value: value.to_string(),
+            });
+        }
+        location
+    }
+
+    #[test]
+    fn valid_location_has_no_issues() {
+        assert_eq!(valid_location().validate(), vec![]);
+    }
+
+    #[test]
+    fn issues_point_at_offending_fields() {
+        let mut location = valid_location();
+        location.name = "  ".into();
+        location.contact_methods.all_contact_methods_mut()[0].value = "kontakt@".to_string();
+        location.opened_hours.set_day_time_span(
+            Day::Friday,
+            TimeSpan {
+                from: TimePoint {
+                    hour: 16,
+                    minute: 0,
+                },
+                to: TimePoint { hour: 8, minute: 0 },
+            },
+        );
+        location.tags = location.tags.without_tag(Tag::new("Gdańsk".to_string()));
+        location.tags.define_tag("LGBT");
+
+        let issues = location.validate();
+        let summary: Vec<(String, Severity)> = issues
+            .iter()
+            .map(|issue| (issue.field.to_string(), issue.severity()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("name".to_string(), Severity::Error),
+                ("contact_methods[0]".to_string(), Severity::Error),
+                ("opened_hours.friday".to_string(), Severity::Error),
+                ("tags".to_string(), Severity::Warning),
+            ]
+        );
+        assert!(has_errors(&issues));
+        assert_eq!(issues[1].kind, IssueKind::MalformedEmail);
+    }
+
+    #[test]
+    fn malformed_email_and_no_tags_block_saving() {
+        let mut location = valid_location();
+        location.contact_methods.all_contact_methods_mut()[0].value = "kontakt@".to_string();
+        assert!(has_errors(&location.validate()));
+
+        let mut location = valid_location();
+        location.tags = location.tags.without_tag(Tag::new("Gdańsk".to_string()));
+        let issues = location.validate();
+        assert!(issues.iter().any(|issue| issue.kind == IssueKind::NoTags));
+        assert!(has_errors(&issues));
+    }
+
+    #[test]
+    fn phone_numbers_accept_real_world_formats() {
+        for phone_number in [
+            "515\u{202f}760\u{202f}789",
+            " 58/ 511 01 21",
+            "112",
+            "116 123",
+        ] {
+            assert!(is_valid_phone_number(phone_number), "{phone_number}");
+        }
+        assert!(!is_valid_phone_number("12"));
+        assert!(!is_valid_phone_number("58 511 01 2x"));
+    }
+
+    #[test]
+    fn initial_database_has_no_validation_errors() {
+        let bin_data = include_bytes!("../../frontend/src/initial_database.bson").to_vec();
+        let locations = crate::locations::Locations::from_bin_data(bin_data).unwrap();
+        for location in locations.locations.values() {
+            let issues = location.validate();
+            assert!(!has_errors(&issues), "{}: {issues:?}", location.name);
+        }
+    }
+}